@@ -21,8 +21,11 @@ use rand::prelude::*;
 use rand::rngs::adapter::ReseedingRng;
 use rand::rngs::{mock::StepRng, OsRng};
 use rand_chacha::{ChaCha12Rng, ChaCha20Core, ChaCha20Rng, ChaCha8Rng};
+use rand_drbg::{CtrDrbg, HmacDrbg};
 use rand_hc::Hc128Rng;
-use rand_pcg::{Pcg32, Pcg64, Pcg64Mcg};
+use rand_pcg::{Pcg32, Pcg64, Pcg64Dxsm, Pcg64Mcg};
+use rand_random123::{Philox4x32Rng, Threefry4x64Rng};
+use rand_xoshiro::{Xoroshiro128PlusPlus, Xoshiro256PlusPlus, Xoshiro256StarStar};
 
 macro_rules! gen_bytes {
     ($fnn:ident, $gen:expr) => {
@@ -45,10 +48,18 @@ gen_bytes!(gen_bytes_step, StepRng::new(0, 1));
 gen_bytes!(gen_bytes_pcg32, Pcg32::from_entropy());
 gen_bytes!(gen_bytes_pcg64, Pcg64::from_entropy());
 gen_bytes!(gen_bytes_pcg64mcg, Pcg64Mcg::from_entropy());
+gen_bytes!(gen_bytes_pcg64dxsm, Pcg64Dxsm::from_entropy());
+gen_bytes!(gen_bytes_xoshiro256plusplus, Xoshiro256PlusPlus::from_entropy());
+gen_bytes!(gen_bytes_xoshiro256starstar, Xoshiro256StarStar::from_entropy());
+gen_bytes!(gen_bytes_xoroshiro128plusplus, Xoroshiro128PlusPlus::from_entropy());
+gen_bytes!(gen_bytes_philox4x32, Philox4x32Rng::from_entropy());
+gen_bytes!(gen_bytes_threefry4x64, Threefry4x64Rng::from_entropy());
 gen_bytes!(gen_bytes_chacha8, ChaCha8Rng::from_entropy());
 gen_bytes!(gen_bytes_chacha12, ChaCha12Rng::from_entropy());
 gen_bytes!(gen_bytes_chacha20, ChaCha20Rng::from_entropy());
 gen_bytes!(gen_bytes_hc128, Hc128Rng::from_entropy());
+gen_bytes!(gen_bytes_hmac_drbg, HmacDrbg::from_entropy());
+gen_bytes!(gen_bytes_ctr_drbg, CtrDrbg::from_entropy());
 gen_bytes!(gen_bytes_std, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_bytes!(gen_bytes_small, SmallRng::from_entropy());
@@ -75,10 +86,18 @@ gen_uint!(gen_u32_step, u32, StepRng::new(0, 1));
 gen_uint!(gen_u32_pcg32, u32, Pcg32::from_entropy());
 gen_uint!(gen_u32_pcg64, u32, Pcg64::from_entropy());
 gen_uint!(gen_u32_pcg64mcg, u32, Pcg64Mcg::from_entropy());
+gen_uint!(gen_u32_pcg64dxsm, u32, Pcg64Dxsm::from_entropy());
+gen_uint!(gen_u32_xoshiro256plusplus, u32, Xoshiro256PlusPlus::from_entropy());
+gen_uint!(gen_u32_xoshiro256starstar, u32, Xoshiro256StarStar::from_entropy());
+gen_uint!(gen_u32_xoroshiro128plusplus, u32, Xoroshiro128PlusPlus::from_entropy());
+gen_uint!(gen_u32_philox4x32, u32, Philox4x32Rng::from_entropy());
+gen_uint!(gen_u32_threefry4x64, u32, Threefry4x64Rng::from_entropy());
 gen_uint!(gen_u32_chacha8, u32, ChaCha8Rng::from_entropy());
 gen_uint!(gen_u32_chacha12, u32, ChaCha12Rng::from_entropy());
 gen_uint!(gen_u32_chacha20, u32, ChaCha20Rng::from_entropy());
 gen_uint!(gen_u32_hc128, u32, Hc128Rng::from_entropy());
+gen_uint!(gen_u32_hmac_drbg, u32, HmacDrbg::from_entropy());
+gen_uint!(gen_u32_ctr_drbg, u32, CtrDrbg::from_entropy());
 gen_uint!(gen_u32_std, u32, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_uint!(gen_u32_small, u32, SmallRng::from_entropy());
@@ -88,10 +107,18 @@ gen_uint!(gen_u64_step, u64, StepRng::new(0, 1));
 gen_uint!(gen_u64_pcg32, u64, Pcg32::from_entropy());
 gen_uint!(gen_u64_pcg64, u64, Pcg64::from_entropy());
 gen_uint!(gen_u64_pcg64mcg, u64, Pcg64Mcg::from_entropy());
+gen_uint!(gen_u64_pcg64dxsm, u64, Pcg64Dxsm::from_entropy());
+gen_uint!(gen_u64_xoshiro256plusplus, u64, Xoshiro256PlusPlus::from_entropy());
+gen_uint!(gen_u64_xoshiro256starstar, u64, Xoshiro256StarStar::from_entropy());
+gen_uint!(gen_u64_xoroshiro128plusplus, u64, Xoroshiro128PlusPlus::from_entropy());
+gen_uint!(gen_u64_philox4x32, u64, Philox4x32Rng::from_entropy());
+gen_uint!(gen_u64_threefry4x64, u64, Threefry4x64Rng::from_entropy());
 gen_uint!(gen_u64_chacha8, u64, ChaCha8Rng::from_entropy());
 gen_uint!(gen_u64_chacha12, u64, ChaCha12Rng::from_entropy());
 gen_uint!(gen_u64_chacha20, u64, ChaCha20Rng::from_entropy());
 gen_uint!(gen_u64_hc128, u64, Hc128Rng::from_entropy());
+gen_uint!(gen_u64_hmac_drbg, u64, HmacDrbg::from_entropy());
+gen_uint!(gen_u64_ctr_drbg, u64, CtrDrbg::from_entropy());
 gen_uint!(gen_u64_std, u64, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_uint!(gen_u64_small, u64, SmallRng::from_entropy());
@@ -113,6 +140,12 @@ macro_rules! init_gen {
 init_gen!(init_pcg32, Pcg32);
 init_gen!(init_pcg64, Pcg64);
 init_gen!(init_pcg64mcg, Pcg64Mcg);
+init_gen!(init_pcg64dxsm, Pcg64Dxsm);
+init_gen!(init_xoshiro256plusplus, Xoshiro256PlusPlus);
+init_gen!(init_xoshiro256starstar, Xoshiro256StarStar);
+init_gen!(init_xoroshiro128plusplus, Xoroshiro128PlusPlus);
+init_gen!(init_philox4x32, Philox4x32Rng);
+init_gen!(init_threefry4x64, Threefry4x64Rng);
 init_gen!(init_hc128, Hc128Rng);
 init_gen!(init_chacha, ChaCha20Rng);
 
@@ -14,7 +14,7 @@ const RAND_BENCH_N: u64 = 1000;
 
 use test::Bencher;
 
-use rand::distributions::{Bernoulli, Distribution, Standard};
+use rand::distributions::{Bernoulli, Distribution, Standard, Uniform};
 use rand::prelude::*;
 use rand_pcg::{Pcg32, Pcg64Mcg};
 
@@ -96,6 +96,45 @@ fn misc_bernoulli_var(b: &mut Bencher) {
     })
 }
 
+// Single samples from a range which is not known at compile time, via
+// `gen_range` (`UniformSampler::sample_single`) and via a `Uniform` which is
+// only used once.
+macro_rules! gen_range_int {
+    ($fnn_single:ident, $fnn_uniform:ident, $ty:ident) => {
+        #[bench]
+        fn $fnn_single(b: &mut Bencher) {
+            let mut rng = Pcg64Mcg::from_rng(&mut thread_rng()).unwrap();
+            let high = $ty::MAX / 3;
+            b.iter(|| {
+                let mut accum: $ty = 0;
+                for _ in 0..crate::RAND_BENCH_N {
+                    accum = accum.wrapping_add(rng.gen_range(0, test::black_box(high)));
+                }
+                accum
+            })
+        }
+
+        #[bench]
+        fn $fnn_uniform(b: &mut Bencher) {
+            let mut rng = Pcg64Mcg::from_rng(&mut thread_rng()).unwrap();
+            let high = $ty::MAX / 3;
+            b.iter(|| {
+                let mut accum: $ty = 0;
+                for _ in 0..crate::RAND_BENCH_N {
+                    accum = accum.wrapping_add(Uniform::new(0, test::black_box(high)).sample(&mut rng));
+                }
+                accum
+            })
+        }
+    };
+}
+
+gen_range_int!(misc_gen_range_u8, misc_uniform_once_u8, u8);
+gen_range_int!(misc_gen_range_u16, misc_uniform_once_u16, u16);
+gen_range_int!(misc_gen_range_u32, misc_uniform_once_u32, u32);
+gen_range_int!(misc_gen_range_u64, misc_uniform_once_u64, u64);
+gen_range_int!(misc_gen_range_u128, misc_uniform_once_u128, u128);
+
 #[bench]
 fn gen_1kb_u16_iter_repeat(b: &mut Bencher) {
     use std::iter;
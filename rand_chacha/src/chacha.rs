@@ -15,6 +15,11 @@ use self::core::fmt;
 use crate::guts::ChaCha;
 use rand_core::block::{BlockRng, BlockRngCore};
 use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1")] use self::core::marker::PhantomData;
+#[cfg(feature = "serde1")]
+use serde::de::{self, SeqAccess, Visitor};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 const STREAM_PARAM_NONCE: u32 = 1;
 const STREAM_PARAM_BLOCK: u32 = 0;
@@ -62,6 +67,64 @@ impl<T> fmt::Debug for Array64<T> {
     }
 }
 
+// serde only implements its traits for arrays of up to 32 elements, so we
+// (de)serialize the buffer as a tuple by hand.
+#[cfg(feature = "serde1")]
+impl<T: Serialize> Serialize for Array64<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+        let mut tuple = serializer.serialize_tuple(64)?;
+        for x in self.0.iter() {
+            tuple.serialize_element(x)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de, T> Deserialize<'de> for Array64<T>
+where T: Deserialize<'de> + Default
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Array64Visitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for Array64Visitor<T>
+        where T: Deserialize<'de> + Default
+        {
+            type Value = Array64<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length 64")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut array = Array64::default();
+                for (i, x) in array.0.iter_mut().enumerate() {
+                    *x = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                Ok(array)
+            }
+        }
+
+        deserializer.deserialize_tuple(64, Array64Visitor(PhantomData))
+    }
+}
+
+/// The externally visible state of a ChaCha generator, used for serialization.
+///
+/// Rather than exposing the internal (possibly SIMD) representation, we store
+/// the key, the stream id and the position in the stream, which is enough to
+/// reconstruct the generator exactly.
+#[cfg(feature = "serde1")]
+#[derive(Serialize, Deserialize)]
+struct ChaChaState {
+    seed: [u8; 32],
+    stream: u64,
+    word_pos: u128,
+}
+
 macro_rules! chacha_impl {
     ($ChaChaXCore:ident, $ChaChaXRng:ident, $rounds:expr, $doc:expr) => {
         #[doc=$doc]
@@ -102,6 +165,31 @@ macro_rules! chacha_impl {
 
         impl CryptoRng for $ChaChaXCore {}
 
+        #[cfg(feature = "serde1")]
+        impl Serialize for $ChaChaXCore {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let block = self.state.get_stream_param(STREAM_PARAM_BLOCK);
+                ChaChaState {
+                    seed: self.state.get_seed(),
+                    stream: self.state.get_stream_param(STREAM_PARAM_NONCE),
+                    word_pos: u128::from(block) << 4,
+                }
+                .serialize(serializer)
+            }
+        }
+
+        #[cfg(feature = "serde1")]
+        impl<'de> Deserialize<'de> for $ChaChaXCore {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                ChaChaState::deserialize(deserializer).map(|s| {
+                    let mut core = $ChaChaXCore::from_seed(s.seed);
+                    core.state.set_stream_param(STREAM_PARAM_NONCE, s.stream);
+                    core.state.set_stream_param(STREAM_PARAM_BLOCK, (s.word_pos >> 4) as u64);
+                    core
+                })
+            }
+        }
+
         /// A cryptographically secure random number generator that uses the ChaCha algorithm.
         ///
         /// ChaCha is a stream cipher designed by Daniel J. Bernstein[^1], that we use as an RNG. It is
@@ -187,10 +275,11 @@ macro_rules! chacha_impl {
             /// byte-offset.
             #[inline]
             pub fn get_word_pos(&self) -> u128 {
-                let mut block = u128::from(self.rng.core.state.get_stream_param(STREAM_PARAM_BLOCK));
-                // counter is incremented *after* filling buffer
-                block -= 4;
-                (block << 4) + self.rng.index() as u128
+                let block = u128::from(self.rng.core.state.get_stream_param(STREAM_PARAM_BLOCK));
+                // counter is incremented *after* filling buffer; before the
+                // first refill this wraps around, which the mask undoes
+                let block = block.wrapping_sub(4);
+                ((block << 4).wrapping_add(self.rng.index() as u128)) & ((1 << 68) - 1)
             }
 
             /// Set the offset from the start of the stream, in 32-bit words.
@@ -230,10 +319,46 @@ macro_rules! chacha_impl {
                     self.set_word_pos(wp);
                 }
             }
+
+            /// Get the stream number.
+            #[inline]
+            pub fn get_stream(&self) -> u64 {
+                self.rng.core.state.get_stream_param(STREAM_PARAM_NONCE)
+            }
+
+            /// Get the seed.
+            #[inline]
+            pub fn get_seed(&self) -> [u8; 32] {
+                self.rng.core.state.get_seed()
+            }
         }
 
         impl CryptoRng for $ChaChaXRng {}
 
+        #[cfg(feature = "serde1")]
+        impl Serialize for $ChaChaXRng {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                ChaChaState {
+                    seed: self.get_seed(),
+                    stream: self.get_stream(),
+                    word_pos: self.get_word_pos(),
+                }
+                .serialize(serializer)
+            }
+        }
+
+        #[cfg(feature = "serde1")]
+        impl<'de> Deserialize<'de> for $ChaChaXRng {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                ChaChaState::deserialize(deserializer).map(|s| {
+                    let mut rng = $ChaChaXRng::from_seed(s.seed);
+                    rng.set_stream(s.stream);
+                    rng.set_word_pos(s.word_pos);
+                    rng
+                })
+            }
+        }
+
         impl From<$ChaChaXCore> for $ChaChaXRng {
             fn from(core: $ChaChaXCore) -> Self {
                 $ChaChaXRng {
@@ -456,4 +581,65 @@ mod test {
             assert_eq!(rng.next_u32(), clone.next_u32());
         }
     }
+
+    #[test]
+    fn test_chacha_word_pos_fresh() {
+        let rng = ChaChaRng::from_seed([7u8; 32]);
+        assert_eq!(rng.get_word_pos(), 0);
+    }
+
+    #[test]
+    fn test_chacha_get_seed_stream() {
+        let mut seed = [0u8; 32];
+        for (i, x) in seed.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let mut rng = ChaChaRng::from_seed(seed);
+        assert_eq!(rng.get_seed(), seed);
+        assert_eq!(rng.get_stream(), 0);
+        rng.set_stream(0x0123_4567_89ab_cdef);
+        assert_eq!(rng.get_stream(), 0x0123_4567_89ab_cdef);
+        assert_eq!(rng.get_seed(), seed);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_chacha_serde_roundtrip() {
+        use super::ChaCha20Core;
+        use rand_core::block::BlockRngCore;
+
+        let mut rng = ChaChaRng::from_seed([19u8; 32]);
+        // a fresh generator
+        let encoded = bincode::serialize(&rng).expect("Could not serialize");
+        let mut decoded: ChaChaRng = bincode::deserialize(&encoded).expect("Could not deserialize");
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), decoded.next_u64());
+        }
+
+        // part way through a block, on a non-default stream
+        rng.set_stream(42);
+        for _ in 0..7 {
+            rng.next_u32();
+        }
+        let encoded = bincode::serialize(&rng).expect("Could not serialize");
+        let mut decoded: ChaChaRng = bincode::deserialize(&encoded).expect("Could not deserialize");
+        assert_eq!(rng.get_word_pos(), decoded.get_word_pos());
+        assert_eq!(rng.get_stream(), decoded.get_stream());
+        for _ in 0..100 {
+            assert_eq!(rng.next_u32(), decoded.next_u32());
+        }
+
+        // the core alone
+        let mut core = ChaCha20Core::from_seed([3u8; 32]);
+        let mut results = Default::default();
+        core.generate(&mut results);
+        let encoded = bincode::serialize(&core).expect("Could not serialize");
+        let mut decoded: ChaCha20Core =
+            bincode::deserialize(&encoded).expect("Could not deserialize");
+        let mut expected = Default::default();
+        let mut actual = Default::default();
+        core.generate(&mut expected);
+        decoded.generate(&mut actual);
+        assert_eq!(expected.as_ref(), actual.as_ref());
+    }
 }
@@ -91,6 +91,11 @@ impl ChaCha {
     pub fn get_stream_param(&self, param: u32) -> u64 {
         get_stream_param(self, param)
     }
+
+    #[inline(always)]
+    pub fn get_seed(&self) -> [u8; 32] {
+        get_seed(self)
+    }
 }
 
 #[inline(always)]
@@ -196,6 +201,19 @@ dispatch_light128!(m, Mach, {
     }
 });
 
+dispatch_light128!(m, Mach, {
+    fn get_seed(state: &ChaCha) -> [u8; 32] {
+        let b: Mach::u32x4 = m.unpack(state.b);
+        let c: Mach::u32x4 = m.unpack(state.c);
+        let mut key = [0u8; 32];
+        for i in 0..4 {
+            key[i * 4..(i + 1) * 4].copy_from_slice(&b.extract(i as u32).to_le_bytes());
+            key[16 + i * 4..16 + (i + 1) * 4].copy_from_slice(&c.extract(i as u32).to_le_bytes());
+        }
+        key
+    }
+});
+
 fn read_u32le(xs: &[u8]) -> u32 {
     assert_eq!(xs.len(), 4);
     u32::from(xs[0]) | (u32::from(xs[1]) << 8) | (u32::from(xs[2]) << 16) | (u32::from(xs[3]) << 24)
@@ -0,0 +1,285 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Non-blocking sources of random bytes.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{Error, SeedableRng};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))] use alloc::boxed::Box;
+
+/// A source of random bytes which may not be ready, such as an entropy
+/// daemon reached through a socket.
+///
+/// This is the non-blocking counterpart of [`RngCore::try_fill_bytes`], for
+/// use in asynchronous code: instead of blocking the thread until the bytes
+/// are available, [`poll_fill_bytes`] returns [`Poll::Pending`] and arranges
+/// for the task to be woken, like `AsyncRead::poll_read` of the `futures`
+/// crate. The futures returned by [`fill_bytes`] and [`seed_rng`] fill a
+/// whole buffer or seed.
+///
+/// Requires the `async` feature, which is available since Rust 1.36.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(dead_code)]
+/// use rand_core::{AsyncRngCore, SplitMix64};
+///
+/// async fn seed_pool<R: AsyncRngCore + Unpin>(source: &mut R)
+///     -> Result<Vec<SplitMix64>, rand_core::Error>
+/// {
+///     let mut pool = Vec::new();
+///     for _ in 0..4 {
+///         pool.push(source.seed_rng::<SplitMix64>().await?);
+///     }
+///     Ok(pool)
+/// }
+/// ```
+///
+/// [`RngCore::try_fill_bytes`]: crate::RngCore::try_fill_bytes
+/// [`poll_fill_bytes`]: AsyncRngCore::poll_fill_bytes
+/// [`fill_bytes`]: AsyncRngCore::fill_bytes
+/// [`seed_rng`]: AsyncRngCore::seed_rng
+pub trait AsyncRngCore {
+    /// Attempt to fill a prefix of `dest` with random data.
+    ///
+    /// On success, returns `Poll::Ready(Ok(n))`, where `n` bytes at the start
+    /// of `dest` have been filled. `n` must be at least 1 if `dest` is not
+    /// empty; a source which has run out of data must report an error.
+    ///
+    /// If no data is available yet, returns `Poll::Pending` and arranges for
+    /// the current task to be woken when data may be available.
+    fn poll_fill_bytes(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, dest: &mut [u8],
+    ) -> Poll<Result<usize, Error>>;
+
+    /// Fill all of `dest` with random data.
+    ///
+    /// The returned future resolves when `dest` is full, or on the first
+    /// error, in which case the contents of `dest` are unspecified.
+    fn fill_bytes<'a>(&'a mut self, dest: &'a mut [u8]) -> FillBytes<'a, Self>
+    where Self: Unpin {
+        FillBytes {
+            rng: self,
+            dest,
+            filled: 0,
+        }
+    }
+
+    /// Create a new PRNG seeded from this source.
+    ///
+    /// This is the asynchronous counterpart of [`SeedableRng::from_rng`].
+    fn seed_rng<S: SeedableRng>(&mut self) -> SeedRng<'_, Self, S>
+    where Self: Unpin {
+        SeedRng {
+            rng: self,
+            seed: Some(S::Seed::default()),
+            filled: 0,
+        }
+    }
+}
+
+/// Fill `dest[*filled..]`, advancing `filled`, until `dest` is full.
+fn poll_fill<R: AsyncRngCore + Unpin + ?Sized>(
+    rng: &mut R, cx: &mut Context<'_>, dest: &mut [u8], filled: &mut usize,
+) -> Poll<Result<(), Error>> {
+    while *filled < dest.len() {
+        match Pin::new(&mut *rng).poll_fill_bytes(cx, &mut dest[*filled..]) {
+            Poll::Ready(Ok(n)) => {
+                assert!(n > 0, "AsyncRngCore::poll_fill_bytes filled no bytes");
+                *filled += n;
+            }
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Future for [`AsyncRngCore::fill_bytes`].
+#[must_use = "futures do nothing unless polled"]
+pub struct FillBytes<'a, R: ?Sized> {
+    rng: &'a mut R,
+    dest: &'a mut [u8],
+    filled: usize,
+}
+
+impl<'a, R: ?Sized> core::fmt::Debug for FillBytes<'a, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // The bytes filled so far are not shown, since they are secret.
+        f.debug_struct("FillBytes").field("filled", &self.filled).finish()
+    }
+}
+
+impl<'a, R: AsyncRngCore + Unpin + ?Sized> Future for FillBytes<'a, R> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        poll_fill(this.rng, cx, this.dest, &mut this.filled)
+    }
+}
+
+/// Future for [`AsyncRngCore::seed_rng`].
+#[must_use = "futures do nothing unless polled"]
+pub struct SeedRng<'a, R: ?Sized, S: SeedableRng> {
+    rng: &'a mut R,
+    // `None` once completed
+    seed: Option<S::Seed>,
+    filled: usize,
+}
+
+// The seed is never pinned.
+impl<'a, R: ?Sized, S: SeedableRng> Unpin for SeedRng<'a, R, S> {}
+
+impl<'a, R: ?Sized, S: SeedableRng> core::fmt::Debug for SeedRng<'a, R, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // The seed is not shown, since it is secret.
+        f.debug_struct("SeedRng").field("filled", &self.filled).finish()
+    }
+}
+
+impl<'a, R: AsyncRngCore + Unpin + ?Sized, S: SeedableRng> Future for SeedRng<'a, R, S> {
+    type Output = Result<S, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let seed = this.seed.as_mut().expect("SeedRng polled after completion");
+        match poll_fill(this.rng, cx, seed.as_mut(), &mut this.filled) {
+            Poll::Ready(Ok(())) => {
+                let seed = this.seed.take().unwrap();
+                Poll::Ready(Ok(S::from_seed(seed)))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<R: AsyncRngCore + Unpin + ?Sized> AsyncRngCore for &mut R {
+    #[inline(always)]
+    fn poll_fill_bytes(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, dest: &mut [u8],
+    ) -> Poll<Result<usize, Error>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_bytes(cx, dest)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: AsyncRngCore + Unpin + ?Sized> AsyncRngCore for Box<R> {
+    #[inline(always)]
+    fn poll_fill_bytes(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, dest: &mut [u8],
+    ) -> Poll<Result<usize, Error>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_bytes(cx, dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SplitMix64;
+    use crate::RngCore;
+    use core::num::NonZeroU32;
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    /// Poll `future` until it is ready, returning the result and the number
+    /// of times it was pending.
+    fn block_on<F: Future + Unpin>(mut future: F) -> (F::Output, usize) {
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+        let mut pending = 0;
+        loop {
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+                return (output, pending);
+            }
+            pending += 1;
+        }
+    }
+
+    /// Yields the bytes 0, 1, 2, ... at most 3 at a time, and is pending
+    /// every other call (starting with the second); fails after `limit` bytes.
+    struct SlowSource {
+        next: u8,
+        ready: bool,
+        limit: usize,
+    }
+
+    impl AsyncRngCore for SlowSource {
+        fn poll_fill_bytes(
+            self: Pin<&mut Self>, cx: &mut Context<'_>, dest: &mut [u8],
+        ) -> Poll<Result<usize, Error>> {
+            let this = self.get_mut();
+            this.ready = !this.ready;
+            if !this.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if this.limit == 0 {
+                let code = NonZeroU32::new(Error::CUSTOM_START).unwrap();
+                return Poll::Ready(Err(code.into()));
+            }
+            let n = dest.len().min(3).min(this.limit);
+            for x in dest[..n].iter_mut() {
+                *x = this.next;
+                this.next = this.next.wrapping_add(1);
+            }
+            this.limit -= n;
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut source = SlowSource { next: 0, ready: false, limit: 100 };
+        let mut buf = [0u8; 10];
+        let (result, pending) = block_on(source.fill_bytes(&mut buf));
+        assert!(result.is_ok());
+        assert_eq!(buf, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(pending, 3);
+
+        // via `&mut R`
+        let mut buf = [0u8; 2];
+        let mut by_ref = &mut source;
+        let (result, _) = block_on(<&mut SlowSource>::fill_bytes(&mut by_ref, &mut buf));
+        assert!(result.is_ok());
+        assert_eq!(buf, [10, 11]);
+
+        let mut buf = [0u8; 100];
+        let (result, _) = block_on(source.fill_bytes(&mut buf));
+        assert_eq!(result.unwrap_err().code().unwrap().get(), Error::CUSTOM_START);
+    }
+
+    #[test]
+    fn test_seed_rng() {
+        let mut source = SlowSource { next: 0, ready: false, limit: 100 };
+        let (result, _) = block_on(source.seed_rng::<SplitMix64>());
+        let mut seed = [0u8; 8];
+        for (i, x) in seed.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        assert_eq!(result.unwrap().next_u64(), SplitMix64::from_seed(seed).next_u64());
+
+        let mut source = SlowSource { next: 0, ready: false, limit: 4 };
+        let (result, _) = block_on(source.seed_rng::<SplitMix64>());
+        assert!(result.is_err());
+    }
+}
@@ -47,11 +47,38 @@
 //! type MyRng = BlockRng<u32, MyRngCore>;
 //! ```
 //!
+//! # Stream semantics
+//!
+//! The output of a [`BlockRngCore`] is a sequence of words, which defines a
+//! byte stream: the concatenation of the words in little-endian byte order.
+//! This stream is the same on every platform. The wrappers consume it as
+//! follows:
+//!
+//! -   [`BlockRng`] consumes whole `u32` words in order: one per
+//!     [`next_u32`], two per [`next_u64`] (least significant first) and
+//!     `ceil(n / 4)` per [`fill_bytes`] of `n` bytes, discarding the unused
+//!     bytes of the last word.
+//! -   [`BlockRng64`] consumes whole `u64` words in order: one per
+//!     [`next_u64`] and `ceil(n / 8)` per [`fill_bytes`] of `n` bytes. Two
+//!     consecutive [`next_u32`] calls use the halves of one word (the least
+//!     significant half first); the unused half of a word is discarded by
+//!     the next call of another method.
+//! -   [`BlockRngBytes`] consumes exactly the bytes requested: 4 per
+//!     [`next_u32`], 8 per [`next_u64`] (both little-endian) and `n` per
+//!     [`fill_bytes`] of `n` bytes, never discarding output. Any sequence of
+//!     calls thus reads the byte stream without gaps.
+//!
+//! Hence, for calls which do not discard output (such as [`fill_bytes`] of
+//! multiples of 8 bytes) all three wrappers produce the same bytes.
+//!
 //! [`BlockRngCore`]: crate::block::BlockRngCore
+//! [`next_u32`]: RngCore::next_u32
+//! [`next_u64`]: RngCore::next_u64
 //! [`fill_bytes`]: RngCore::fill_bytes
 
 use crate::impls::{fill_via_u32_chunks, fill_via_u64_chunks};
 use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use core::cmp::min;
 use core::convert::AsRef;
 use core::fmt;
 #[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
@@ -354,15 +381,9 @@ where <R as BlockRngCore>::Results: AsRef<[u64]> + AsMut<[u64]>
         self.half_used = !self.half_used;
         self.index += self.half_used as usize;
 
-        // Index as if this is a u32 slice.
-        unsafe {
-            let results = &*(self.results.as_ref() as *const [u64] as *const [u32]);
-            if cfg!(target_endian = "little") {
-                *results.get_unchecked(index)
-            } else {
-                *results.get_unchecked(index ^ 1)
-            }
-        }
+        // Index as if this is a u32 slice of the little-endian words.
+        let value = self.results.as_ref()[index / 2];
+        (value >> (32 * (index % 2))) as u32
     }
 
     #[inline]
@@ -425,3 +446,309 @@ impl<R: BlockRngCore + SeedableRng> SeedableRng for BlockRng64<R> {
 }
 
 impl<R: BlockRngCore + CryptoRng> CryptoRng for BlockRng<R> {}
+
+
+/// The word types of [`BlockRngCore`] results supported by
+/// [`BlockRngBytes`]: `u32` and `u64`.
+pub trait BlockWord: Copy + private::Sealed {
+    /// The size of the word in bytes.
+    const BYTES: usize;
+
+    #[doc(hidden)]
+    fn fill_via_chunks(src: &[Self], dest: &mut [u8]) -> (usize, usize);
+
+    #[doc(hidden)]
+    fn le_bytes(self) -> [u8; 8];
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+}
+
+impl BlockWord for u32 {
+    const BYTES: usize = 4;
+
+    #[inline(always)]
+    fn fill_via_chunks(src: &[u32], dest: &mut [u8]) -> (usize, usize) {
+        fill_via_u32_chunks(src, dest)
+    }
+
+    #[inline(always)]
+    fn le_bytes(self) -> [u8; 8] {
+        u64::from(self).to_le_bytes()
+    }
+}
+
+impl BlockWord for u64 {
+    const BYTES: usize = 8;
+
+    #[inline(always)]
+    fn fill_via_chunks(src: &[u64], dest: &mut [u8]) -> (usize, usize) {
+        fill_via_u64_chunks(src, dest)
+    }
+
+    #[inline(always)]
+    fn le_bytes(self) -> [u8; 8] {
+        self.to_le_bytes()
+    }
+}
+
+/// A wrapper type implementing [`RngCore`] for some type implementing
+/// [`BlockRngCore`] with `u32` or `u64` array buffer, which reads the output
+/// as a pure byte stream.
+///
+/// Unlike [`BlockRng`] and [`BlockRng64`], which consume whole words, every
+/// method consumes exactly the number of bytes it returns: [`next_u32`]
+/// reads 4 bytes and [`next_u64`] 8 bytes (in little-endian order), and
+/// [`fill_bytes`] and [`try_fill_bytes`] read the requested number of bytes.
+/// No output is discarded, however calls of different widths are mixed. The
+/// byte stream is the little-endian encoding of the generated words, so the
+/// output is the same on all platforms. See the
+/// [module documentation](crate::block#stream-semantics).
+///
+/// This is slightly slower than [`BlockRng`] and [`BlockRng64`] for
+/// [`next_u32`] and [`next_u64`].
+///
+/// [`next_u32`]: RngCore::next_u32
+/// [`next_u64`]: RngCore::next_u64
+/// [`fill_bytes`]: RngCore::fill_bytes
+/// [`try_fill_bytes`]: RngCore::try_fill_bytes
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BlockRngBytes<R: BlockRngCore + ?Sized> {
+    results: R::Results,
+    index: usize, // in bytes
+    /// The *core* part of the RNG, implementing the `generate` function.
+    pub core: R,
+}
+
+// Custom Debug implementation that does not expose the contents of `results`.
+impl<R: BlockRngCore + fmt::Debug> fmt::Debug for BlockRngBytes<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BlockRngBytes")
+            .field("core", &self.core)
+            .field("result_len", &self.results.as_ref().len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<R: BlockRngCore> BlockRngBytes<R>
+where R::Item: BlockWord
+{
+    /// Create a new `BlockRngBytes` from an existing RNG implementing
+    /// `BlockRngCore`. Results will be generated on first use.
+    #[inline]
+    pub fn new(core: R) -> BlockRngBytes<R> {
+        let results_empty = R::Results::default();
+        BlockRngBytes {
+            core,
+            index: results_empty.as_ref().len() * R::Item::BYTES,
+            results: results_empty,
+        }
+    }
+
+    /// Get the index into the result buffer, in bytes.
+    ///
+    /// If this is equal to or larger than the size of the result buffer in
+    /// bytes then the buffer is "empty" and `generate()` must be called to
+    /// produce new results.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Reset the number of available results.
+    /// This will force a new set of results to be generated on next use.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.index = self.results.as_ref().len() * R::Item::BYTES;
+    }
+}
+
+impl<R: BlockRngCore> RngCore for BlockRngBytes<R>
+where R::Item: BlockWord
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let size = R::Item::BYTES;
+        let mut read_len = 0;
+        while read_len < dest.len() {
+            if self.index >= self.results.as_ref().len() * size {
+                self.core.generate(&mut self.results);
+                self.index = 0;
+            }
+            let results = self.results.as_ref();
+            let (word, offset) = (self.index / size, self.index % size);
+            let filled_u8 = if offset == 0 {
+                R::Item::fill_via_chunks(&results[word..], &mut dest[read_len..]).1
+            } else {
+                // Read the rest of a partially consumed word.
+                let n = min(size - offset, dest.len() - read_len);
+                let bytes = results[word].le_bytes();
+                dest[read_len..read_len + n].copy_from_slice(&bytes[offset..offset + n]);
+                n
+            };
+            self.index += filled_u8;
+            read_len += filled_u8;
+        }
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<R: BlockRngCore + SeedableRng> SeedableRng for BlockRngBytes<R>
+where R::Item: BlockWord
+{
+    type Seed = R::Seed;
+
+    #[inline(always)]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(R::from_seed(seed))
+    }
+
+    #[inline(always)]
+    fn seed_from_u64(seed: u64) -> Self {
+        Self::new(R::seed_from_u64(seed))
+    }
+
+    #[inline(always)]
+    fn from_rng<S: RngCore>(rng: S) -> Result<Self, Error> {
+        Ok(Self::new(R::from_rng(rng)?))
+    }
+}
+
+impl<R: BlockRngCore + CryptoRng> CryptoRng for BlockRngBytes<R> {}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Generate words such that the byte stream is 0, 1, 2, ...
+    #[derive(Debug, Clone)]
+    struct Counter32(u32);
+    #[derive(Debug, Clone)]
+    struct Counter64(u64);
+
+    impl BlockRngCore for Counter32 {
+        type Item = u32;
+        type Results = [u32; 3];
+
+        fn generate(&mut self, results: &mut Self::Results) {
+            for r in results.iter_mut() {
+                *r = u32::from_le_bytes(pattern(self.0 as u8));
+                self.0 += 1;
+            }
+        }
+    }
+
+    impl BlockRngCore for Counter64 {
+        type Item = u64;
+        type Results = [u64; 3];
+
+        fn generate(&mut self, results: &mut Self::Results) {
+            for r in results.iter_mut() {
+                let lo = u64::from(u32::from_le_bytes(pattern(2 * self.0 as u8)));
+                let hi = u64::from(u32::from_le_bytes(pattern(2 * self.0 as u8 + 1)));
+                *r = (hi << 32) | lo;
+                self.0 += 1;
+            }
+        }
+    }
+
+    fn pattern(n: u8) -> [u8; 4] {
+        [4 * n, 4 * n + 1, 4 * n + 2, 4 * n + 3]
+    }
+
+    /// The byte stream of both counters.
+    fn stream() -> [u8; 96] {
+        let mut bytes = [0u8; 96];
+        for (i, x) in bytes.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        bytes
+    }
+
+    /// Read with a mix of call widths, returning the bytes read.
+    fn read_mixed<R: RngCore>(rng: &mut R) -> ([u8; 96], usize) {
+        let mut out = [0u8; 96];
+        let mut pos = 0;
+        for &len in &[1, 4, 3, 8, 0, 5, 17, 2, 4, 8, 1, 7] {
+            match len {
+                4 => out[pos..pos + 4].copy_from_slice(&rng.next_u32().to_le_bytes()),
+                8 => out[pos..pos + 8].copy_from_slice(&rng.next_u64().to_le_bytes()),
+                _ => rng.fill_bytes(&mut out[pos..pos + len]),
+            }
+            pos += len;
+        }
+        (out, pos)
+    }
+
+    #[test]
+    fn test_block_rng_bytes_stream() {
+        let expected = stream();
+
+        let (out, len) = read_mixed(&mut BlockRngBytes::new(Counter32(0)));
+        assert_eq!(out[..len], expected[..len]);
+        let (out, len) = read_mixed(&mut BlockRngBytes::new(Counter64(0)));
+        assert_eq!(out[..len], expected[..len]);
+
+        let mut rng = BlockRngBytes::new(Counter64(0));
+        let mut out = [0u8; 96];
+        for chunk in out.chunks_mut(5) {
+            rng.fill_bytes(chunk);
+        }
+        assert_eq!(out[..], expected[..]);
+    }
+
+    #[test]
+    fn test_block_rng_stream() {
+        // Without discarding output, all wrappers read the same stream.
+        let expected = stream();
+        let mut rng32 = BlockRng::new(Counter32(0));
+        let mut rng64 = BlockRng64::new(Counter64(0));
+        let mut bytes = BlockRngBytes::new(Counter32(0));
+        for chunk in expected.chunks(8).take(6) {
+            assert_eq!(rng32.next_u64().to_le_bytes(), chunk);
+            assert_eq!(rng64.next_u64().to_le_bytes(), chunk);
+            assert_eq!(bytes.next_u64().to_le_bytes(), chunk);
+        }
+        for chunk in expected[48..].chunks(4) {
+            assert_eq!(rng32.next_u32().to_le_bytes(), chunk);
+            assert_eq!(rng64.next_u32().to_le_bytes(), chunk);
+            assert_eq!(bytes.next_u32().to_le_bytes(), chunk);
+        }
+
+        // `BlockRng` and `BlockRng64` discard the rest of partially used
+        // words.
+        let mut rng32 = BlockRng::new(Counter32(0));
+        let mut rng64 = BlockRng64::new(Counter64(0));
+        let mut out = [0u8; 3];
+        rng32.fill_bytes(&mut out);
+        rng64.fill_bytes(&mut out);
+        assert_eq!(rng32.next_u32().to_le_bytes(), expected[4..8]);
+        assert_eq!(rng64.next_u32().to_le_bytes(), expected[8..12]);
+        assert_eq!(rng64.next_u64().to_le_bytes(), expected[16..24]);
+    }
+}
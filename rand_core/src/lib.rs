@@ -25,6 +25,15 @@
 //! The [`impls`] and [`le`] sub-modules include a few small functions to assist
 //! implementation of [`RngCore`].
 //!
+//! [`SplitMix64`] is a simple, fast PRNG, mainly of use for expanding small
+//! seeds into the state of other generators.
+//!
+//! [`SeedSequence`] derives a reproducible tree of independent seeds from a
+//! single root seed.
+//!
+//! With the `async` feature, [`AsyncRngCore`] is the non-blocking counterpart
+//! of [`RngCore`], for sources which may not be ready.
+//!
 //! [`rand`]: https://docs.rs/rand
 
 #![doc(
@@ -45,15 +54,23 @@ use core::default::Default;
 #[cfg(all(feature = "alloc", not(feature = "std")))] extern crate alloc;
 #[cfg(all(feature = "alloc", not(feature = "std")))] use alloc::boxed::Box;
 
+#[cfg(feature = "async")] pub use async_rng::{AsyncRngCore, FillBytes, SeedRng};
 pub use error::Error;
 #[cfg(feature = "getrandom")] pub use os::OsRng;
+#[cfg(feature = "std")] pub use os::{reset_entropy_source, set_entropy_source};
+pub use seed_seq::SeedSequence;
+pub use splitmix::SplitMix64;
 
 
+#[cfg(feature = "async")] mod async_rng;
 pub mod block;
 mod error;
 pub mod impls;
 pub mod le;
 #[cfg(feature = "getrandom")] mod os;
+mod seed_seq;
+mod siphash;
+mod splitmix;
 
 
 /// The core of a random number generator.
@@ -319,6 +336,32 @@ pub trait SeedableRng: Sized {
         Self::from_seed(seed)
     }
 
+    /// Create a new PRNG by hashing a byte string, e.g. a human-readable name.
+    ///
+    /// This is a convenience-wrapper around `from_seed` to derive a seed of
+    /// any size from input of any length. Every bit of the seed depends on
+    /// every input byte, and different inputs yield independent-looking seeds,
+    /// so e.g. `seed_from_bytes(b"experiment-1")` and
+    /// `seed_from_bytes(b"experiment-2")` give unrelated generators.
+    ///
+    /// The seed is computed as follows, such that it can be reproduced in
+    /// other languages: bytes `8 * i .. 8 * i + 8` of the seed are the
+    /// little-endian bytes of SipHash-2-4 of the input, with the key formed by
+    /// the 16 little-endian bytes of `i as u128` (the last chunk is truncated
+    /// if the seed size is not a multiple of 8). This output is stable across
+    /// versions and platforms; *changing* it is a value-breaking change.
+    ///
+    /// This **is not suitable for cryptography**: SipHash is a keyed hash, but
+    /// the keys used here are public.
+    fn seed_from_bytes(bytes: &[u8]) -> Self {
+        let mut seed = Self::Seed::default();
+        for (i, chunk) in seed.as_mut().chunks_mut(8).enumerate() {
+            let x = siphash::siphash24(i as u64, 0, bytes);
+            chunk.copy_from_slice(&x.to_le_bytes()[..chunk.len()]);
+        }
+        Self::from_seed(seed)
+    }
+
     /// Create a new PRNG seeded from another `Rng`.
     ///
     /// This may be useful when needing to rapidly seed many PRNGs from a master
@@ -349,7 +392,8 @@ pub trait SeedableRng: Sized {
         Ok(Self::from_seed(seed))
     }
 
-    /// Creates a new instance of the RNG seeded via [`getrandom`].
+    /// Creates a new instance of the RNG seeded via [`getrandom`] (or the
+    /// source installed with [`set_entropy_source`]).
     ///
     /// This method is the recommended way to construct non-deterministic PRNGs
     /// since it is convenient and secure.
@@ -362,14 +406,33 @@ pub trait SeedableRng: Sized {
     ///
     /// If [`getrandom`] is unable to provide secure entropy this method will panic.
     ///
+    /// [`try_from_entropy`] is a variant reporting the error instead.
+    ///
     /// [`getrandom`]: https://docs.rs/getrandom
+    /// [`set_entropy_source`]: crate::set_entropy_source
+    /// [`try_from_entropy`]: SeedableRng::try_from_entropy
     #[cfg(feature = "getrandom")]
     fn from_entropy() -> Self {
+        Self::try_from_entropy().unwrap_or_else(|err|
+            panic!("from_entropy failed: {}", err))
+    }
+
+    /// Creates a new instance of the RNG seeded via [`getrandom`], without
+    /// panicking.
+    ///
+    /// This is identical to [`from_entropy`], except that failure to obtain
+    /// entropy is reported as an [`Error`], which keeps the error code of
+    /// [`getrandom`] (see [`Error::code`] and [`Error::raw_os_error`]).
+    /// Applications which may run with restricted access to system entropy
+    /// (e.g. early at boot or in a sandbox) can thus recover.
+    ///
+    /// [`getrandom`]: https://docs.rs/getrandom
+    /// [`from_entropy`]: SeedableRng::from_entropy
+    #[cfg(feature = "getrandom")]
+    fn try_from_entropy() -> Result<Self, Error> {
         let mut seed = Self::Seed::default();
-        if let Err(err) = getrandom::getrandom(seed.as_mut()) {
-            panic!("from_entropy failed: {}", err);
-        }
-        Self::from_seed(seed)
+        OsRng.try_fill_bytes(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
     }
 }
 
@@ -483,4 +546,57 @@ mod test {
         // value-breakage test:
         assert_eq!(results[0], 5029875928683246316);
     }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_try_from_entropy() {
+        let mut a = SplitMix64::try_from_entropy().unwrap();
+        let mut b = SplitMix64::try_from_entropy().unwrap();
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn test_seed_from_bytes() {
+        struct SeedableBytes([u8; 20]);
+        impl SeedableRng for SeedableBytes {
+            type Seed = [u8; 20];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                SeedableBytes(seed)
+            }
+        }
+
+        const INPUTS: [&[u8]; 6] = [b"", b"\0", b"\0\0", b"a", b"b", b"experiment-1"];
+        let mut results = [[0u8; 20]; 6];
+        for (i, input) in INPUTS.iter().enumerate() {
+            results[i] = SeedableBytes::seed_from_bytes(input).0;
+        }
+
+        for (i1, r1) in results.iter().enumerate() {
+            let weight: u32 = r1.iter().map(|x| x.count_ones()).sum();
+            // B(160, 0.5): the chance of weight < 50 or > 110 is ~1e-6
+            assert!(weight >= 50 && weight <= 110);
+
+            for (i2, r2) in results.iter().enumerate() {
+                if i1 == i2 {
+                    continue;
+                }
+                let diff_weight: u32 = r1.iter().zip(r2.iter())
+                    .map(|(x, y)| (x ^ y).count_ones()).sum();
+                assert!(diff_weight >= 50);
+            }
+        }
+
+        // The seed starts with the SipHash-2-4 outputs for keys 0, 1, ...
+        let x0 = siphash::siphash24(0, 0, b"experiment-1").to_le_bytes();
+        let x2 = siphash::siphash24(2, 0, b"experiment-1").to_le_bytes();
+        assert_eq!(results[5][..8], x0);
+        assert_eq!(results[5][16..], x2[..4]);
+
+        // value-breakage test:
+        assert_eq!(results[0], [
+            215, 0, 119, 115, 157, 75, 146, 30, 222, 163,
+            28, 75, 172, 97, 231, 84, 225, 60, 155, 138,
+        ]);
+    }
 }
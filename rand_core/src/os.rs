@@ -10,6 +10,7 @@
 
 use crate::{impls, CryptoRng, Error, RngCore};
 use getrandom::getrandom;
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
 
 /// A random number generator that retrieves randomness from the
 /// operating system.
@@ -19,6 +20,11 @@ use getrandom::getrandom;
 /// The implementation is provided by the [getrandom] crate. Refer to
 /// [getrandom] documentation for details.
 ///
+/// With the `std` feature, another source of entropy can be substituted
+/// process-wide with [`set_entropy_source`], e.g. a hardware RNG or a fake
+/// source for testing. `OsRng` (and thus everything seeded via it, such as
+/// `SeedableRng::from_entropy`) then uses that source instead of getrandom.
+///
 /// This struct is only available when specifying the crate feature `getrandom`
 /// or `std`. When using the `rand` lib, it is also available as `rand::rngs::OsRng`.
 ///
@@ -43,7 +49,9 @@ use getrandom::getrandom;
 /// ```
 ///
 /// [getrandom]: https://crates.io/crates/getrandom
+/// [`set_entropy_source`]: crate::set_entropy_source
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct OsRng;
 
 impl CryptoRng for OsRng {}
@@ -64,11 +72,94 @@ impl RngCore for OsRng {
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        #[cfg(feature = "std")] {
+            if let Some(result) = source::try_fill_bytes(dest) {
+                return result;
+            }
+        }
         getrandom(dest)?;
         Ok(())
     }
 }
 
+/// Replace the source of entropy used by [`OsRng`] in this process.
+///
+/// All subsequent uses of `OsRng`, in any thread, read from `source` instead
+/// of the operating system, until [`reset_entropy_source`] is called. This
+/// includes seeding via `SeedableRng::from_entropy`, and in `rand`, seeding
+/// and reseeding of `thread_rng` and of `ReseedingRng`s using `OsRng`.
+///
+/// The source is used behind a lock, hence it should not itself use `OsRng`
+/// (which would deadlock). Errors reported by `source.try_fill_bytes` are
+/// passed on to the users of `OsRng`.
+///
+/// Note that the source is trusted: `OsRng` implements [`CryptoRng`]
+/// regardless of the quality of the installed source.
+///
+/// # Example
+///
+/// ```
+/// use rand_core::{RngCore, OsRng, SplitMix64};
+///
+/// // fake entropy for testing
+/// rand_core::set_entropy_source(Box::new(SplitMix64::new(42)));
+/// let x = OsRng.next_u64();
+/// rand_core::reset_entropy_source();
+/// assert_eq!(x, SplitMix64::new(42).next_u64());
+/// ```
+#[cfg(feature = "std")]
+pub fn set_entropy_source(source: Box<dyn RngCore + Send>) {
+    source::set(Some(source))
+}
+
+/// Restore the operating system as the source of entropy for [`OsRng`],
+/// removing the source installed by [`set_entropy_source`], if any.
+#[cfg(feature = "std")]
+pub fn reset_entropy_source() {
+    source::set(None)
+}
+
+#[cfg(feature = "std")]
+mod source {
+    use crate::{Error, RngCore};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Mutex, MutexGuard, Once};
+
+    type Source = Box<dyn RngCore + Send>;
+
+    // Avoids locking in the common case of no source being installed.
+    static HAS_SOURCE: AtomicBool = AtomicBool::new(false);
+
+    fn lock() -> MutexGuard<'static, Option<Source>> {
+        // `Mutex::new` is not `const` on our minimum supported Rust version.
+        static INIT: Once = Once::new();
+        static mut SOURCE: *const Mutex<Option<Source>> = std::ptr::null();
+        let source = unsafe {
+            INIT.call_once(|| {
+                SOURCE = Box::into_raw(Box::new(Mutex::new(None)));
+            });
+            &*SOURCE
+        };
+        // A panicking source does not leave the `Option` in a broken state.
+        source.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(source: Option<Source>) {
+        let mut guard = lock();
+        HAS_SOURCE.store(source.is_some(), Ordering::Release);
+        *guard = source;
+    }
+
+    /// Fill `dest` from the installed source, or return `None` if there is
+    /// none.
+    pub fn try_fill_bytes(dest: &mut [u8]) -> Option<Result<(), Error>> {
+        if !HAS_SOURCE.load(Ordering::Acquire) {
+            return None;
+        }
+        lock().as_mut().map(|source| source.try_fill_bytes(dest))
+    }
+}
+
 #[test]
 fn test_os_rng() {
     let x = OsRng.next_u64();
@@ -0,0 +1,271 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hierarchical seed derivation.
+
+use crate::SeedableRng;
+
+const POOL_SIZE: usize = 4;
+const INIT_A: u32 = 0x43b0d7e5;
+const MULT_A: u32 = 0x931e8875;
+const INIT_B: u32 = 0x8b51f9dd;
+const MULT_B: u32 = 0x58f38ded;
+const MIX_MULT_L: u32 = 0xca01f9dd;
+const MIX_MULT_R: u32 = 0x4973f715;
+const XSHIFT: u32 = 16;
+
+/// A tree of reproducible, independent seeds derived from a root seed.
+///
+/// A `SeedSequence` is constructed from some root entropy, and identifies a
+/// node in a tree of seeds: [`SeedSequence::child`] derives the node with the
+/// given index below it, so e.g. "run 3 / worker 17 / stream 2" is
+/// `root.child(3).child(17).child(2)`. Each node can seed any [`SeedableRng`]
+/// via [`SeedSequence::create_rng`]. Distinct nodes (including a node and its
+/// descendants) produce independent seeds; in particular, the resulting
+/// generators are not correlated like those created by chaining
+/// [`SeedableRng::from_rng`] can be.
+///
+/// The algorithm is that of NumPy's `SeedSequence` (which in turn is based on
+/// Melissa O'Neill's `seed_seq_fe`), with a pool of four 32-bit words. A node
+/// corresponds to a NumPy `SeedSequence` with the same entropy words and a
+/// `spawn_key` of its child indices, and [`SeedSequence::generate_state`] to
+/// `generate_state(n, numpy.uint32)`. The output depends only on the entropy
+/// and the path, never on the platform; changing it is a value-breaking
+/// change.
+///
+/// The mixing function is not cryptographically secure; the root entropy is
+/// assumed to be either random (e.g. from `OsRng`) or a user-chosen value.
+///
+/// # Example
+///
+/// ```
+/// use rand_core::{RngCore, SeedSequence, SplitMix64};
+///
+/// let root = SeedSequence::new(&[12345]);
+/// for worker in 0..4 {
+///     let node = root.child(worker);
+///     let mut rng: SplitMix64 = node.child(0).create_rng();
+///     let x = rng.next_u64();
+/// }
+///
+/// // spawn children with consecutive indices
+/// let mut run = root.child(3);
+/// let a = run.spawn();
+/// let b = run.spawn();
+/// assert_eq!(b.pool(), run.child(1).pool());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeedSequence {
+    pool: [u32; POOL_SIZE],
+    // The mixing state, needed to mix in the indices of children.
+    hash_const: u32,
+    n_children_spawned: u32,
+}
+
+#[inline]
+fn hashmix(value: u32, hash_const: &mut u32) -> u32 {
+    let mut value = value ^ *hash_const;
+    *hash_const = hash_const.wrapping_mul(MULT_A);
+    value = value.wrapping_mul(*hash_const);
+    value ^ (value >> XSHIFT)
+}
+
+#[inline]
+fn mix(x: u32, y: u32) -> u32 {
+    let result = MIX_MULT_L
+        .wrapping_mul(x)
+        .wrapping_sub(MIX_MULT_R.wrapping_mul(y));
+    result ^ (result >> XSHIFT)
+}
+
+impl SeedSequence {
+    /// Create the root of a tree of seeds from the given entropy words.
+    ///
+    /// The entropy may be of any length; for best results it should contain
+    /// at least 128 bits of entropy if it is random. An integer seed `n`
+    /// corresponds to the words of `n` in little-endian order (e.g. `&[n]` if
+    /// `n` fits in 32 bits), as in NumPy.
+    pub fn new(entropy: &[u32]) -> Self {
+        let mut pool = [0; POOL_SIZE];
+        let mut hash_const = INIT_A;
+
+        // Add in the entropy up to the pool size, padding with zeros.
+        for (i, word) in pool.iter_mut().enumerate() {
+            let value = entropy.get(i).cloned().unwrap_or(0);
+            *word = hashmix(value, &mut hash_const);
+        }
+        // Mix all bits together so later words can affect earlier ones.
+        for i_src in 0..POOL_SIZE {
+            for i_dst in 0..POOL_SIZE {
+                if i_src != i_dst {
+                    let value = hashmix(pool[i_src], &mut hash_const);
+                    pool[i_dst] = mix(pool[i_dst], value);
+                }
+            }
+        }
+
+        let mut seq = SeedSequence {
+            pool,
+            hash_const,
+            n_children_spawned: 0,
+        };
+        for &word in entropy.iter().skip(POOL_SIZE) {
+            seq.mix_in(word);
+        }
+        seq
+    }
+
+    /// Mix another word into each word of the pool.
+    fn mix_in(&mut self, value: u32) {
+        for i_dst in 0..POOL_SIZE {
+            let value = hashmix(value, &mut self.hash_const);
+            self.pool[i_dst] = mix(self.pool[i_dst], value);
+        }
+    }
+
+    /// Derive the child with the given index.
+    ///
+    /// This is deterministic: calling it twice with the same index yields the
+    /// same child. Each level of the tree adds exactly one word to the
+    /// `spawn_key`, hence distinct paths below the same root never mix in the
+    /// same words. (Entropy words beyond the fourth are mixed in the same way
+    /// as child indices, so roots with different numbers of entropy words
+    /// should not be combined.)
+    pub fn child(&self, index: u32) -> SeedSequence {
+        let mut child = SeedSequence {
+            pool: self.pool,
+            hash_const: self.hash_const,
+            n_children_spawned: 0,
+        };
+        child.mix_in(index);
+        child
+    }
+
+    /// Derive the next child which was not yet spawned.
+    ///
+    /// The children are indexed from zero, such that the `n`-th call to
+    /// `spawn` returns `self.child(n)`; children created via
+    /// [`SeedSequence::child`] are not taken into account.
+    pub fn spawn(&mut self) -> SeedSequence {
+        let child = self.child(self.n_children_spawned);
+        self.n_children_spawned += 1;
+        child
+    }
+
+    /// Return the entropy pool, summarizing the root entropy and the path.
+    pub fn pool(&self) -> [u32; POOL_SIZE] {
+        self.pool
+    }
+
+    /// Fill `dest` with words derived from this node.
+    ///
+    /// The output does not depend on the length of `dest`, other than that
+    /// a longer output extends a shorter one.
+    pub fn generate_state(&self, dest: &mut [u32]) {
+        let mut hash_const = INIT_B;
+        for (word, &src) in dest.iter_mut().zip(self.pool.iter().cycle()) {
+            let mut value = src ^ hash_const;
+            hash_const = hash_const.wrapping_mul(MULT_B);
+            value = value.wrapping_mul(hash_const);
+            *word = value ^ (value >> XSHIFT);
+        }
+    }
+
+    /// Fill `dest` with bytes derived from this node.
+    ///
+    /// These are the bytes of the words produced by
+    /// [`SeedSequence::generate_state`] in little-endian order.
+    pub fn fill_bytes(&self, dest: &mut [u8]) {
+        let mut hash_const = INIT_B;
+        for (chunk, &src) in dest.chunks_mut(4).zip(self.pool.iter().cycle()) {
+            let mut value = src ^ hash_const;
+            hash_const = hash_const.wrapping_mul(MULT_B);
+            value = value.wrapping_mul(hash_const);
+            value ^= value >> XSHIFT;
+            chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    /// Create a new PRNG seeded from this node, using [`SeedableRng::from_seed`]
+    /// with the output of [`SeedSequence::fill_bytes`].
+    pub fn create_rng<R: SeedableRng>(&self) -> R {
+        let mut seed = R::Seed::default();
+        self.fill_bytes(seed.as_mut());
+        R::from_seed(seed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SeedSequence;
+    use crate::{RngCore, SplitMix64};
+
+    #[test]
+    fn test_seed_seq_value_stability() {
+        // Values computed with a direct port of NumPy's `SeedSequence`, where
+        // the child indices form the `spawn_key`.
+        let root = SeedSequence::new(&[12345]);
+        assert_eq!(root.pool(), [0xf293d734, 0xfd668225, 0xf94ff895, 0x872090a0]);
+        let mut state = [0u32; 6];
+        root.generate_state(&mut state);
+        assert_eq!(state, [0xa03d837c, 0xb5ae6482, 0xfa1f7a2f, 0xbbe2996f,
+                           0x37158f94, 0x64e39a9f]);
+
+        let node = root.child(3).child(17).child(2);
+        assert_eq!(node.pool(), [0xdf9ba857, 0x0e49e8be, 0x107f56d9, 0x0b359d88]);
+
+        let empty = SeedSequence::new(&[]);
+        assert_eq!(empty.pool(), [0xfe40eb07, 0x4f363a36, 0x4eb2009d, 0xc89a7aa7]);
+        assert_eq!(empty.pool(), SeedSequence::new(&[0, 0, 0, 0]).pool());
+
+        // more than `POOL_SIZE` entropy words
+        let node = SeedSequence::new(&[1, 2, 3, 4, 5, 6]).child(1 << 31);
+        assert_eq!(node.pool(), [0xfa7819bc, 0xa7fde0c5, 0x90121130, 0xeb68ea0e]);
+
+        let node = SeedSequence::new(&[0xdeadbeef, 0xcafebabe]).child(0);
+        assert_eq!(node.pool(), [0x392418d6, 0xa9f38b1f, 0xd04eb5a8, 0xa2a59cda]);
+        let mut bytes = [0u8; 7];
+        node.fill_bytes(&mut bytes);
+        assert_eq!(bytes, [0xf8, 0xbf, 0x6b, 0x52, 0x82, 0x06, 0xdc]);
+    }
+
+    #[test]
+    fn test_seed_seq_children() {
+        let mut root = SeedSequence::new(&[1, 2]);
+        let nodes = [
+            root.clone(),
+            root.child(0),
+            root.child(1),
+            root.child(!0),
+            root.child(0).child(1),
+            root.child(1).child(0),
+            root.child(0).child(0),
+        ];
+        for (i1, n1) in nodes.iter().enumerate() {
+            for n2 in nodes[i1 + 1..].iter() {
+                assert_ne!(n1.pool(), n2.pool());
+            }
+        }
+
+        assert_eq!(root.spawn(), root.child(0));
+        assert_eq!(root.spawn(), root.child(1));
+        // spawned children start counting their own children from zero
+        let mut child = root.spawn();
+        assert_eq!(child.spawn(), root.child(2).child(0));
+    }
+
+    #[test]
+    fn test_seed_seq_create_rng() {
+        let node = SeedSequence::new(&[42]).child(7);
+        let mut words = [0u32; 2];
+        node.generate_state(&mut words);
+        let mut rng: SplitMix64 = node.create_rng();
+        let mut expected = SplitMix64::new(u64::from(words[0]) | u64::from(words[1]) << 32);
+        assert_eq!(rng.next_u64(), expected.next_u64());
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SipHash-2-4, used by `SeedableRng::seed_from_bytes`.
+//!
+//! `core::hash::SipHasher` is deprecated and its output is not guaranteed to
+//! be stable, hence this small implementation, following the reference
+//! implementation at <https://github.com/veorq/SipHash>.
+
+struct State {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+}
+
+impl State {
+    #[inline]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        self.round();
+        self.round();
+        self.v0 ^= m;
+    }
+}
+
+/// SipHash-2-4 of `msg` with the 128-bit key `(k0, k1)`, where `k0` holds
+/// the first 8 key bytes in little-endian order.
+pub(crate) fn siphash24(k0: u64, k1: u64, msg: &[u8]) -> u64 {
+    let mut s = State {
+        v0: k0 ^ 0x736f6d6570736575,
+        v1: k1 ^ 0x646f72616e646f6d,
+        v2: k0 ^ 0x6c7967656e657261,
+        v3: k1 ^ 0x7465646279746573,
+    };
+
+    let mut chunks = msg.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(chunk);
+        s.compress(u64::from_le_bytes(buf));
+    }
+    let rem = chunks.remainder();
+    let mut buf = [0u8; 8];
+    buf[..rem.len()].copy_from_slice(rem);
+    buf[7] = msg.len() as u8;
+    s.compress(u64::from_le_bytes(buf));
+
+    s.v2 ^= 0xff;
+    for _ in 0..4 {
+        s.round();
+    }
+    s.v0 ^ s.v1 ^ s.v2 ^ s.v3
+}
+
+#[cfg(test)]
+mod test {
+    use super::siphash24;
+
+    #[test]
+    fn test_siphash24_reference() {
+        // Test vectors from the SipHash paper and reference implementation:
+        // key 00 01 .. 0f, message 00 01 .. (n-1).
+        let k0 = 0x0706050403020100;
+        let k1 = 0x0f0e0d0c0b0a0908;
+        let msg: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        assert_eq!(siphash24(k0, k1, &[]), 0x726fdb47dd0e0e31);
+        assert_eq!(siphash24(k0, k1, &msg[..15]), 0xa129ca6149be45e5);
+    }
+}
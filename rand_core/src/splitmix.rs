@@ -0,0 +1,189 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The SplitMix64 random number generator.
+
+use crate::{impls, le, Error, RngCore, SeedableRng};
+use core::fmt;
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
+
+/// A splitmix64 random number generator.
+///
+/// The splitmix algorithm is not suitable for cryptographic purposes, but is
+/// very fast and has a 64 bit state. Every state is valid, including zero,
+/// making it a good choice for expanding a small seed into the state of
+/// another generator (it is recommended for this purpose by the authors of
+/// the xoshiro generators).
+///
+/// The state can be set in `const` contexts via [`SplitMix64::new`], and
+/// [`SplitMix64::split`] cheaply derives a new, differently positioned,
+/// generator. Neither requires `std` or `alloc`.
+///
+/// The algorithm used here is translated from [the `splitmix64.c`
+/// reference source code](http://xoshiro.di.unimi.it/splitmix64.c) by
+/// Sebastiano Vigna.
+///
+/// # Example
+///
+/// ```
+/// use rand_core::{RngCore, SplitMix64};
+///
+/// const SEEDER: SplitMix64 = SplitMix64::new(42);
+///
+/// let mut rng = SEEDER.clone();
+/// let mut child = rng.split();
+/// let x = child.next_u64();
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SplitMix64 {
+    x: u64,
+}
+
+const PHI: u64 = 0x9e3779b97f4a7c15;
+
+impl SplitMix64 {
+    /// Construct a `SplitMix64` with the given state.
+    ///
+    /// This is equivalent to `seed_from_u64(state)`, but is usable in `const`
+    /// contexts.
+    #[inline]
+    pub const fn new(state: u64) -> Self {
+        SplitMix64 { x: state }
+    }
+
+    /// Derive a new generator from this one.
+    ///
+    /// The new generator's state is the next output of `self`, hence this
+    /// costs a single call to `next_u64`. Since all `SplitMix64` instances
+    /// traverse the same cycle of length 2<sup>64</sup>, the child is a jump
+    /// to an unpredictable position in that cycle; the probability that the
+    /// outputs of two generators overlap is negligible for sequences much
+    /// shorter than 2<sup>32</sup>.
+    #[inline]
+    pub fn split(&mut self) -> Self {
+        SplitMix64::new(self.next_u64())
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for SplitMix64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SplitMix64 {{}}")
+    }
+}
+
+impl RngCore for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.x = self.x.wrapping_add(PHI);
+        let mut z = self.x;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    /// Create a new `SplitMix64`, reading the state as a little-endian `u64`.
+    fn from_seed(seed: [u8; 8]) -> SplitMix64 {
+        let mut state = [0; 1];
+        le::read_u64_into(&seed, &mut state);
+        SplitMix64::new(state[0])
+    }
+
+    /// Seed a `SplitMix64` from a `u64` seed.
+    ///
+    /// The seed is used directly as the state; unlike most generators,
+    /// splitmix64 produces good output from any state, including zero.
+    fn seed_from_u64(seed: u64) -> SplitMix64 {
+        SplitMix64::new(seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference() {
+        let mut rng = SplitMix64::seed_from_u64(1477776061723855037);
+        // These values were produced with the reference implementation:
+        // http://xoshiro.di.unimi.it/splitmix64.c
+        let expected: [u64; 5] = [
+            1985237415132408290,
+            2979275885539914483,
+            13511426838097143398,
+            8488337342461049707,
+            15141737807933549159,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn seed_from_u64_matches_from_seed() {
+        let mut rng1 = SplitMix64::seed_from_u64(1234567);
+        let mut rng2 = SplitMix64::from_seed(1234567u64.to_le_bytes());
+        let expected: [u64; 5] = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+        ];
+        for &e in &expected {
+            assert_eq!(rng1.next_u64(), e);
+            assert_eq!(rng2.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn const_new() {
+        const RNG: SplitMix64 = SplitMix64::new(1234567);
+        let mut rng1 = RNG.clone();
+        let mut rng2 = SplitMix64::seed_from_u64(1234567);
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+    }
+
+    #[test]
+    fn split() {
+        let mut rng = SplitMix64::seed_from_u64(0);
+        let mut clone = rng.clone();
+        let mut child = rng.split();
+        // The child is seeded from the parent's next output:
+        let mut expected = SplitMix64::new(clone.next_u64());
+        for _ in 0..16 {
+            assert_eq!(child.next_u64(), expected.next_u64());
+        }
+        // The parent is advanced by one step:
+        assert_eq!(rng.next_u64(), clone.next_u64());
+        assert_ne!(rng.next_u64(), child.next_u64());
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! AES-256 encryption (FIPS 197).
+//!
+//! This is a straightforward implementation using the S-box as a lookup
+//! table. Table lookups depend on secret data, so this implementation is not
+//! hardened against cache-timing attacks.
+
+pub const KEY_LEN: usize = 32;
+pub const BLOCK_LEN: usize = 16;
+const ROUNDS: usize = 14;
+
+#[rustfmt::skip]
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const RCON: [u8; 7] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40];
+
+/// An expanded AES-256 key.
+#[derive(Clone)]
+pub struct Aes256 {
+    round_keys: [[u8; BLOCK_LEN]; ROUNDS + 1],
+}
+
+#[inline]
+fn xtime(x: u8) -> u8 {
+    (x << 1) ^ (((x >> 7) & 1) * 0x1b)
+}
+
+impl Aes256 {
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        // The key schedule in 4-byte words.
+        let mut w = [[0u8; 4]; 4 * (ROUNDS + 1)];
+        for (word, chunk) in w.iter_mut().zip(key.chunks_exact(4)) {
+            word.copy_from_slice(chunk);
+        }
+        for i in 8..w.len() {
+            let mut temp = w[i - 1];
+            if i % 8 == 0 {
+                temp = [
+                    SBOX[temp[1] as usize] ^ RCON[i / 8 - 1],
+                    SBOX[temp[2] as usize],
+                    SBOX[temp[3] as usize],
+                    SBOX[temp[0] as usize],
+                ];
+            } else if i % 8 == 4 {
+                for t in temp.iter_mut() {
+                    *t = SBOX[*t as usize];
+                }
+            }
+            for j in 0..4 {
+                w[i][j] = w[i - 8][j] ^ temp[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; BLOCK_LEN]; ROUNDS + 1];
+        for (round_key, words) in round_keys.iter_mut().zip(w.chunks_exact(4)) {
+            for (chunk, word) in round_key.chunks_exact_mut(4).zip(words.iter()) {
+                chunk.copy_from_slice(word);
+            }
+        }
+        Aes256 { round_keys }
+    }
+
+    pub fn encrypt(&self, block: &mut [u8; BLOCK_LEN]) {
+        add_round_key(block, &self.round_keys[0]);
+        for round_key in self.round_keys[1..ROUNDS].iter() {
+            sub_bytes_shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, round_key);
+        }
+        sub_bytes_shift_rows(block);
+        add_round_key(block, &self.round_keys[ROUNDS]);
+    }
+}
+
+#[inline]
+fn add_round_key(block: &mut [u8; BLOCK_LEN], round_key: &[u8; BLOCK_LEN]) {
+    for (b, k) in block.iter_mut().zip(round_key.iter()) {
+        *b ^= k;
+    }
+}
+
+// The state is stored column by column: byte `4 * c + r` is row `r` of
+// column `c`.
+#[inline]
+fn sub_bytes_shift_rows(block: &mut [u8; BLOCK_LEN]) {
+    let s = *block;
+    for c in 0..4 {
+        for r in 0..4 {
+            block[4 * c + r] = SBOX[s[4 * ((c + r) % 4) + r] as usize];
+        }
+    }
+}
+
+#[inline]
+fn mix_columns(block: &mut [u8; BLOCK_LEN]) {
+    for column in block.chunks_exact_mut(4) {
+        let a = [column[0], column[1], column[2], column[3]];
+        let all = a[0] ^ a[1] ^ a[2] ^ a[3];
+        for r in 0..4 {
+            column[r] = a[r] ^ all ^ xtime(a[r] ^ a[(r + 1) % 4]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aes256() {
+        // FIPS 197, appendix C.3
+        let mut key = [0u8; KEY_LEN];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = i as u8;
+        }
+        let mut block = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+        ];
+        Aes256::new(&key).encrypt(&mut block);
+        assert_eq!(block, [
+            0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf,
+            0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89,
+        ]);
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! CTR_DRBG with AES-256, without derivation function.
+
+use core::fmt;
+use core::num::NonZeroU32;
+use rand_core::block::{BlockRng, BlockRngCore};
+use rand_core::{le, CryptoRng, Error, RngCore, SeedableRng};
+
+use crate::aes::{Aes256, BLOCK_LEN, KEY_LEN};
+use crate::{DrbgSeed, MAX_REQUEST_LEN, RESEED_INTERVAL, RESEED_REQUIRED};
+
+// The seed length: the length of the entropy input, and the maximum length of
+// the personalization string and additional input.
+const SEED_LEN: usize = KEY_LEN + BLOCK_LEN;
+
+/// A cryptographically secure random number generator that uses CTR_DRBG
+/// with AES-256 and without derivation function, as specified in
+/// [NIST SP 800-90A].
+///
+/// Without derivation function, the entropy input must be exactly 48 bytes of
+/// full entropy, and the personalization string and additional input are at
+/// most 48 bytes. The generator can be instantiated with
+/// [`CtrDrbg::new`], or via [`SeedableRng`], where the seed is the entropy
+/// input. [`CtrDrbg::reseed`] and [`CtrDrbg::generate`] take additional
+/// input.
+///
+/// The [`RngCore`] methods produce output in requests of 128 bytes each,
+/// which are buffered; they panic if the DRBG requires reseeding, which
+/// happens after 2<sup>48</sup> requests (use [`CtrDrbgCore`] with
+/// `ReseedingRng` to reseed periodically).
+///
+/// [NIST SP 800-90A]: https://doi.org/10.6028/NIST.SP.800-90Ar1
+#[derive(Clone, Debug)]
+pub struct CtrDrbg(BlockRng<CtrDrbgCore>);
+
+impl CtrDrbg {
+    /// Instantiate the DRBG.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is not 48 bytes long or `personalization_string` is
+    /// longer than 48 bytes.
+    pub fn new(entropy_input: &[u8], personalization_string: &[u8]) -> Self {
+        CtrDrbg(BlockRng::new(CtrDrbgCore::new(entropy_input, personalization_string)))
+    }
+
+    /// Reseed the DRBG, discarding buffered output.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is not 48 bytes long or `additional_input` is
+    /// longer than 48 bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.0.core.reseed(entropy_input, additional_input);
+        self.0.reset();
+    }
+
+    /// Fill `dest` using a single generate request with additional input,
+    /// discarding buffered output.
+    ///
+    /// # Panics
+    ///
+    /// If `dest` is longer than 2<sup>16</sup> bytes or `additional_input`
+    /// is longer than 48 bytes.
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        self.0.reset();
+        self.0.core.generate(dest, additional_input)
+    }
+}
+
+impl RngCore for CtrDrbg {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl SeedableRng for CtrDrbg {
+    type Seed = DrbgSeed;
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        CtrDrbg(BlockRng::<CtrDrbgCore>::from_seed(seed))
+    }
+
+    #[inline]
+    fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+        BlockRng::<CtrDrbgCore>::from_rng(rng).map(CtrDrbg)
+    }
+}
+
+impl CryptoRng for CtrDrbg {}
+
+/// The core of `CtrDrbg`, used with `BlockRng`.
+///
+/// Each block is the output of a generate request of 128 bytes without
+/// additional input.
+#[derive(Clone)]
+pub struct CtrDrbgCore {
+    cipher: Aes256,
+    v: [u8; BLOCK_LEN],
+    reseed_counter: u64,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for CtrDrbgCore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CtrDrbgCore {{}}")
+    }
+}
+
+impl CtrDrbgCore {
+    /// Instantiate the DRBG (`CTR_DRBG_Instantiate_algorithm`).
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is not 48 bytes long or `personalization_string` is
+    /// longer than 48 bytes.
+    pub fn new(entropy_input: &[u8], personalization_string: &[u8]) -> Self {
+        let mut drbg = CtrDrbgCore {
+            cipher: Aes256::new(&[0; KEY_LEN]),
+            v: [0; BLOCK_LEN],
+            reseed_counter: 1,
+        };
+        drbg.update(&seed_material(entropy_input, personalization_string));
+        drbg
+    }
+
+    /// Reseed the DRBG (`CTR_DRBG_Reseed_algorithm`).
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is not 48 bytes long or `additional_input` is
+    /// longer than 48 bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.update(&seed_material(entropy_input, additional_input));
+        self.reseed_counter = 1;
+    }
+
+    /// Fill `dest` in a single request (`CTR_DRBG_Generate_algorithm`).
+    ///
+    /// Returns an error with code [`RESEED_REQUIRED`] if the DRBG must be
+    /// reseeded first.
+    ///
+    /// # Panics
+    ///
+    /// If `dest` is longer than 2<sup>16</sup> bytes or `additional_input`
+    /// is longer than 48 bytes.
+    ///
+    /// [`RESEED_REQUIRED`]: crate::RESEED_REQUIRED
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        assert!(dest.len() <= MAX_REQUEST_LEN, "CTR_DRBG request too long");
+        assert!(additional_input.len() <= SEED_LEN, "CTR_DRBG additional input too long");
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(NonZeroU32::new(RESEED_REQUIRED).unwrap().into());
+        }
+        let mut additional = [0u8; SEED_LEN];
+        additional[..additional_input.len()].copy_from_slice(additional_input);
+        if !additional_input.is_empty() {
+            self.update(&additional);
+        }
+        for chunk in dest.chunks_mut(BLOCK_LEN) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(&additional);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// Increment `V` and encrypt it.
+    #[inline]
+    fn next_block(&mut self) -> [u8; BLOCK_LEN] {
+        for x in self.v.iter_mut().rev() {
+            *x = x.wrapping_add(1);
+            if *x != 0 {
+                break;
+            }
+        }
+        let mut block = self.v;
+        self.cipher.encrypt(&mut block);
+        block
+    }
+
+    /// `CTR_DRBG_Update`.
+    fn update(&mut self, provided_data: &[u8; SEED_LEN]) {
+        let mut temp = [0u8; SEED_LEN];
+        for chunk in temp.chunks_exact_mut(BLOCK_LEN) {
+            chunk.copy_from_slice(&self.next_block());
+        }
+        for (t, p) in temp.iter_mut().zip(provided_data.iter()) {
+            *t ^= p;
+        }
+        let mut key = [0u8; KEY_LEN];
+        key.copy_from_slice(&temp[..KEY_LEN]);
+        self.cipher = Aes256::new(&key);
+        self.v.copy_from_slice(&temp[KEY_LEN..]);
+    }
+}
+
+/// The entropy input XORed with the zero-padded second input.
+fn seed_material(entropy_input: &[u8], input: &[u8]) -> [u8; SEED_LEN] {
+    assert_eq!(entropy_input.len(), SEED_LEN, "CTR_DRBG entropy input must be 48 bytes");
+    assert!(input.len() <= SEED_LEN, "CTR_DRBG input too long");
+    let mut material = [0u8; SEED_LEN];
+    material.copy_from_slice(entropy_input);
+    for (m, x) in material.iter_mut().zip(input.iter()) {
+        *m ^= x;
+    }
+    material
+}
+
+impl BlockRngCore for CtrDrbgCore {
+    type Item = u32;
+    type Results = [u32; 32];
+
+    fn generate(&mut self, results: &mut Self::Results) {
+        let mut bytes = [0u8; 4 * 32];
+        CtrDrbgCore::generate(self, &mut bytes, &[])
+            .unwrap_or_else(|err| panic!("CtrDrbgCore: {}", err));
+        le::read_u32_into(&bytes, results);
+    }
+}
+
+impl SeedableRng for CtrDrbgCore {
+    type Seed = DrbgSeed;
+
+    /// Instantiate the DRBG with the seed as entropy input, without
+    /// personalization string.
+    fn from_seed(seed: Self::Seed) -> Self {
+        CtrDrbgCore::new(&seed.0, &[])
+    }
+}
+
+impl CryptoRng for CtrDrbgCore {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ctr_drbg_block_rng() {
+        let mut seed = DrbgSeed::default();
+        for (i, x) in seed.0.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let mut rng = CtrDrbg::from_seed(seed.clone());
+        let mut core = CtrDrbgCore::new(&seed.0, &[]);
+
+        // The buffered output consists of requests of 128 bytes each.
+        let mut expected = [0u8; 128];
+        let mut bytes = [0u8; 128];
+        for _ in 0..3 {
+            core.generate(&mut expected, &[]).unwrap();
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(bytes[..], expected[..]);
+        }
+
+        // `generate` discards buffered output
+        let _ = rng.next_u32();
+        core.generate(&mut expected, &[]).unwrap();
+        core.generate(&mut expected[..16], b"additional input").unwrap();
+        rng.generate(&mut bytes[..16], b"additional input").unwrap();
+        assert_eq!(bytes[..16], expected[..16]);
+    }
+
+    #[test]
+    fn test_ctr_drbg_counter_carry() {
+        let mut core = CtrDrbgCore::from_seed(DrbgSeed::default());
+        core.v = [0xff; BLOCK_LEN];
+        core.v[0] = 0x12;
+        core.next_block();
+        let mut expected = [0; BLOCK_LEN];
+        expected[0] = 0x13;
+        assert_eq!(core.v, expected);
+    }
+
+    #[test]
+    fn test_ctr_drbg_reseed_required() {
+        let mut core = CtrDrbgCore::from_seed(DrbgSeed::default());
+        core.reseed_counter = RESEED_INTERVAL;
+        let mut bytes = [0u8; 16];
+        assert!(core.generate(&mut bytes, &[]).is_ok());
+        let err = core.generate(&mut bytes, &[]).unwrap_err();
+        assert_eq!(err.code().unwrap().get(), RESEED_REQUIRED);
+        core.reseed(&[0; 48], &[]);
+        assert!(core.generate(&mut bytes, &[]).is_ok());
+    }
+}
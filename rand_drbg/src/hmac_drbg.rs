@@ -0,0 +1,277 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! HMAC_DRBG with SHA-256.
+
+use core::fmt;
+use core::num::NonZeroU32;
+use rand_core::block::{BlockRng, BlockRngCore};
+use rand_core::{le, CryptoRng, Error, RngCore, SeedableRng};
+
+use crate::sha256::{hmac, OUTPUT_LEN};
+use crate::{DrbgSeed, MAX_REQUEST_LEN, RESEED_INTERVAL, RESEED_REQUIRED};
+
+// The security strength in bytes, which is the minimum entropy input length.
+const STRENGTH: usize = 32;
+const MIN_NONCE_LEN: usize = STRENGTH / 2;
+
+/// A cryptographically secure random number generator that uses HMAC_DRBG
+/// with SHA-256, as specified in [NIST SP 800-90A].
+///
+/// The generator can be instantiated with an explicit entropy input, nonce
+/// and personalization string with [`HmacDrbg::new`], or via
+/// [`SeedableRng`], where the 48-byte seed consists of a 32-byte entropy
+/// input followed by a 16-byte nonce, without personalization string.
+/// [`HmacDrbg::reseed`] and [`HmacDrbg::generate`] take additional input.
+///
+/// The [`RngCore`] methods produce output in requests of 128 bytes each,
+/// which are buffered; they panic if the DRBG requires reseeding, which
+/// happens after 2<sup>48</sup> requests (use [`HmacDrbgCore`] with
+/// `ReseedingRng` to reseed periodically).
+///
+/// [NIST SP 800-90A]: https://doi.org/10.6028/NIST.SP.800-90Ar1
+#[derive(Clone, Debug)]
+pub struct HmacDrbg(BlockRng<HmacDrbgCore>);
+
+impl HmacDrbg {
+    /// Instantiate the DRBG.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is shorter than 32 bytes or `nonce` is shorter than
+    /// 16 bytes.
+    pub fn new(entropy_input: &[u8], nonce: &[u8], personalization_string: &[u8]) -> Self {
+        HmacDrbg(BlockRng::new(HmacDrbgCore::new(
+            entropy_input,
+            nonce,
+            personalization_string,
+        )))
+    }
+
+    /// Reseed the DRBG, discarding buffered output.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is shorter than 32 bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.0.core.reseed(entropy_input, additional_input);
+        self.0.reset();
+    }
+
+    /// Fill `dest` using a single generate request with additional input,
+    /// discarding buffered output.
+    ///
+    /// # Panics
+    ///
+    /// If `dest` is longer than 2<sup>16</sup> bytes.
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        self.0.reset();
+        self.0.core.generate(dest, additional_input)
+    }
+}
+
+impl RngCore for HmacDrbg {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl SeedableRng for HmacDrbg {
+    type Seed = DrbgSeed;
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        HmacDrbg(BlockRng::<HmacDrbgCore>::from_seed(seed))
+    }
+
+    #[inline]
+    fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+        BlockRng::<HmacDrbgCore>::from_rng(rng).map(HmacDrbg)
+    }
+}
+
+impl CryptoRng for HmacDrbg {}
+
+/// The core of `HmacDrbg`, used with `BlockRng`.
+///
+/// Each block is the output of a generate request of 128 bytes without
+/// additional input.
+#[derive(Clone)]
+pub struct HmacDrbgCore {
+    key: [u8; OUTPUT_LEN],
+    v: [u8; OUTPUT_LEN],
+    reseed_counter: u64,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for HmacDrbgCore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HmacDrbgCore {{}}")
+    }
+}
+
+impl HmacDrbgCore {
+    /// Instantiate the DRBG (`HMAC_DRBG_Instantiate_algorithm`).
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is shorter than 32 bytes or `nonce` is shorter than
+    /// 16 bytes.
+    pub fn new(entropy_input: &[u8], nonce: &[u8], personalization_string: &[u8]) -> Self {
+        assert!(entropy_input.len() >= STRENGTH, "HMAC_DRBG entropy input too short");
+        assert!(nonce.len() >= MIN_NONCE_LEN, "HMAC_DRBG nonce too short");
+        let mut drbg = HmacDrbgCore {
+            key: [0x00; OUTPUT_LEN],
+            v: [0x01; OUTPUT_LEN],
+            reseed_counter: 1,
+        };
+        drbg.update(&[entropy_input, nonce, personalization_string]);
+        drbg
+    }
+
+    /// Reseed the DRBG (`HMAC_DRBG_Reseed_algorithm`).
+    ///
+    /// # Panics
+    ///
+    /// If `entropy_input` is shorter than 32 bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        assert!(entropy_input.len() >= STRENGTH, "HMAC_DRBG entropy input too short");
+        self.update(&[entropy_input, additional_input]);
+        self.reseed_counter = 1;
+    }
+
+    /// Fill `dest` in a single request (`HMAC_DRBG_Generate_algorithm`).
+    ///
+    /// Returns an error with code [`RESEED_REQUIRED`] if the DRBG must be
+    /// reseeded first.
+    ///
+    /// # Panics
+    ///
+    /// If `dest` is longer than 2<sup>16</sup> bytes.
+    ///
+    /// [`RESEED_REQUIRED`]: crate::RESEED_REQUIRED
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        assert!(dest.len() <= MAX_REQUEST_LEN, "HMAC_DRBG request too long");
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(NonZeroU32::new(RESEED_REQUIRED).unwrap().into());
+        }
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+        for chunk in dest.chunks_mut(OUTPUT_LEN) {
+            self.v = hmac(&self.key, &[&self.v]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional_input]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// `HMAC_DRBG_Update`, where the provided data is the concatenation of
+    /// `data`.
+    fn update(&mut self, data: &[&[u8]]) {
+        self.key = hmac_parts(&self.key, &self.v, 0x00, data);
+        self.v = hmac(&self.key, &[&self.v]);
+        if data.iter().all(|d| d.is_empty()) {
+            return;
+        }
+        self.key = hmac_parts(&self.key, &self.v, 0x01, data);
+        self.v = hmac(&self.key, &[&self.v]);
+    }
+}
+
+/// HMAC of `v || [byte] || data[0] || data[1] || ...`.
+fn hmac_parts(key: &[u8; OUTPUT_LEN], v: &[u8], byte: u8, data: &[&[u8]]) -> [u8; OUTPUT_LEN] {
+    // `data` has at most three parts.
+    let mut parts: [&[u8]; 5] = [v, &[byte], &[], &[], &[]];
+    parts[2..2 + data.len()].copy_from_slice(data);
+    hmac(key, &parts)
+}
+
+impl BlockRngCore for HmacDrbgCore {
+    type Item = u32;
+    type Results = [u32; 32];
+
+    fn generate(&mut self, results: &mut Self::Results) {
+        let mut bytes = [0u8; 4 * 32];
+        HmacDrbgCore::generate(self, &mut bytes, &[])
+            .unwrap_or_else(|err| panic!("HmacDrbgCore: {}", err));
+        le::read_u32_into(&bytes, results);
+    }
+}
+
+impl SeedableRng for HmacDrbgCore {
+    type Seed = DrbgSeed;
+
+    /// Instantiate the DRBG with the first 32 bytes of the seed as entropy
+    /// input and the last 16 as nonce, without personalization string.
+    fn from_seed(seed: Self::Seed) -> Self {
+        HmacDrbgCore::new(&seed.0[..STRENGTH], &seed.0[STRENGTH..], &[])
+    }
+}
+
+impl CryptoRng for HmacDrbgCore {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hmac_drbg_block_rng() {
+        let mut seed = DrbgSeed::default();
+        for (i, x) in seed.0.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let mut rng = HmacDrbg::from_seed(seed.clone());
+        let mut core = HmacDrbgCore::new(&seed.0[..32], &seed.0[32..], &[]);
+
+        // The buffered output consists of requests of 128 bytes each.
+        let mut expected = [0u8; 128];
+        let mut bytes = [0u8; 128];
+        for _ in 0..3 {
+            core.generate(&mut expected, &[]).unwrap();
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(bytes[..], expected[..]);
+        }
+
+        // `generate` discards buffered output
+        let _ = rng.next_u32();
+        core.generate(&mut expected, &[]).unwrap();
+        core.generate(&mut expected[..16], b"additional input").unwrap();
+        rng.generate(&mut bytes[..16], b"additional input").unwrap();
+        assert_eq!(bytes[..16], expected[..16]);
+    }
+
+    #[test]
+    fn test_hmac_drbg_reseed_required() {
+        let mut core = HmacDrbgCore::from_seed(DrbgSeed::default());
+        core.reseed_counter = RESEED_INTERVAL;
+        let mut bytes = [0u8; 16];
+        assert!(core.generate(&mut bytes, &[]).is_ok());
+        let err = core.generate(&mut bytes, &[]).unwrap_err();
+        assert_eq!(err.code().unwrap().get(), RESEED_REQUIRED);
+        core.reseed(&[0; 32], &[]);
+        assert!(core.generate(&mut bytes, &[]).is_ok());
+    }
+}
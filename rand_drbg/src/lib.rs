@@ -0,0 +1,95 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic random bit generators from NIST SP 800-90A.
+//!
+//! This crate provides:
+//!
+//! -   [`HmacDrbg`], HMAC_DRBG with SHA-256,
+//! -   [`CtrDrbg`], CTR_DRBG with AES-256 and without a derivation function.
+//!
+//! Both are cryptographically secure generators with a security strength of
+//! 256 bits, for use where the NIST-approved mechanisms are required;
+//! otherwise, the ChaCha generators of `rand_chacha` are faster. They are
+//! implemented on top of [`rand_core::block::BlockRngCore`]; their cores
+//! [`HmacDrbgCore`] and [`CtrDrbgCore`] can be used with `ReseedingRng`, like
+//! `ChaCha20Core`. The cores also expose the SP 800-90A functions directly:
+//! instantiation with a personalization string, and reseeding and generation
+//! with additional input.
+//!
+//! The implementation is validated against a subset of the NIST CAVP DRBG test
+//! vectors: HMAC_DRBG with SHA-256 and CTR_DRBG with AES-256 and no derivation
+//! function, without personalization string or additional input. The SHA-256 and AES-256 primitives are implemented in this
+//! crate, in portable code without protection against cache-timing side
+//! channels (AES uses table lookups).
+
+#![doc(
+    html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+    html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+    html_root_url = "https://rust-random.github.io/rand/"
+)]
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+#![doc(test(attr(allow(unused_variables), deny(warnings))))]
+#![allow(clippy::unreadable_literal)]
+#![no_std]
+
+pub use rand_core;
+
+mod aes;
+mod ctr_drbg;
+mod hmac_drbg;
+mod sha256;
+
+pub use crate::ctr_drbg::{CtrDrbg, CtrDrbgCore};
+pub use crate::hmac_drbg::{HmacDrbg, HmacDrbgCore};
+
+use core::fmt;
+use rand_core::Error;
+
+/// Error code reported by `generate` when the DRBG must be reseeded, after
+/// 2<sup>48</sup> requests since the last (re)seeding.
+pub const RESEED_REQUIRED: u32 = Error::CUSTOM_START + 0x100;
+
+// The maximum number of requests between reseeds (SP 800-90A, table 2 and 3).
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+// The maximum number of bytes per request (SP 800-90A, table 2 and 3).
+const MAX_REQUEST_LEN: usize = 1 << 16;
+
+/// The seed type of the DRBGs of this crate: 48 bytes.
+///
+/// For [`HmacDrbg`], this is a 32-byte entropy input followed by a 16-byte
+/// nonce; for [`CtrDrbg`], this is the entropy input.
+#[derive(Clone)]
+pub struct DrbgSeed(pub [u8; 48]);
+
+impl Default for DrbgSeed {
+    fn default() -> DrbgSeed {
+        DrbgSeed([0; 48])
+    }
+}
+
+impl AsMut<[u8]> for DrbgSeed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl AsRef<[u8]> for DrbgSeed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+// Custom Debug implementation that does not expose the seed
+impl fmt::Debug for DrbgSeed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DrbgSeed {{}}")
+    }
+}
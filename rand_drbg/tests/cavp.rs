@@ -0,0 +1,120 @@
+//! Tests against the DRBG test vectors in `tests/data`, which are entries of
+//! the NIST CAVP DRBG response files. The parser only reads the section of the
+//! tested algorithm, so the published files can be used unmodified.
+
+use rand_drbg::{CtrDrbgCore, HmacDrbgCore};
+
+/// A test case: the fields of one `COUNT` entry, in order.
+#[derive(Default)]
+struct TestCase {
+    count: u32,
+    entropy_input: Vec<u8>,
+    nonce: Vec<u8>,
+    personalization_string: Vec<u8>,
+    // (EntropyInputReseed, AdditionalInputReseed), if the case reseeds
+    reseed: Option<(Vec<u8>, Vec<u8>)>,
+    additional_input: Vec<Vec<u8>>,
+    returned_bits: Vec<u8>,
+}
+
+fn decode_hex(s: &str) -> Vec<u8> {
+    assert!(s.len() % 2 == 0, "odd-length hex string");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Parse the cases of the `[algorithm]` sections of a response file.
+fn parse(data: &str, algorithm: &str) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut case: Option<TestCase> = None;
+    let mut in_section = false;
+    for line in data.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            // Parameters of the section are `[Name = value]`; the algorithm
+            // header has no '='.
+            let header = &line[1..line.len() - 1];
+            if !header.contains('=') {
+                cases.extend(case.take());
+                in_section = header == algorithm;
+            }
+            continue;
+        }
+        if !in_section || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.splitn(2, '=');
+        let key = parts.next().unwrap().trim();
+        let value = parts.next().expect("missing '='").trim();
+        if key == "COUNT" {
+            cases.extend(case.take());
+            case = Some(TestCase { count: value.parse().unwrap(), ..Default::default() });
+            continue;
+        }
+        let case = case.as_mut().expect("field before COUNT");
+        let value = decode_hex(value);
+        match key {
+            "EntropyInput" => case.entropy_input = value,
+            "Nonce" => case.nonce = value,
+            "PersonalizationString" => case.personalization_string = value,
+            "EntropyInputReseed" => case.reseed = Some((value, Vec::new())),
+            "AdditionalInputReseed" => case.reseed.as_mut().unwrap().1 = value,
+            "AdditionalInput" => case.additional_input.push(value),
+            "ReturnedBits" => case.returned_bits = value,
+            _ => panic!("unknown field {}", key),
+        }
+    }
+    cases.extend(case);
+    cases
+}
+
+#[test]
+fn test_hmac_drbg_vectors() {
+    let mut cases = parse(
+        include_str!("data/drbgvectors_no_reseed/HMAC_DRBG.rsp"),
+        "SHA-256",
+    );
+    cases.extend(parse(
+        include_str!("data/drbgvectors_pr_false/HMAC_DRBG.rsp"),
+        "SHA-256",
+    ));
+    assert_eq!(cases.len(), 14);
+    for case in cases {
+        let mut drbg = HmacDrbgCore::new(
+            &case.entropy_input,
+            &case.nonce,
+            &case.personalization_string,
+        );
+        if let Some((entropy_input, additional_input)) = case.reseed {
+            drbg.reseed(&entropy_input, &additional_input);
+        }
+        let mut output = vec![0u8; case.returned_bits.len()];
+        for additional_input in case.additional_input.iter() {
+            drbg.generate(&mut output, additional_input).unwrap();
+        }
+        assert!(output == case.returned_bits, "HMAC_DRBG COUNT = {}", case.count);
+    }
+}
+
+#[test]
+fn test_ctr_drbg_vectors() {
+    let cases = parse(
+        include_str!("data/drbgvectors_no_reseed/CTR_DRBG.rsp"),
+        "AES-256 no df",
+    );
+    assert_eq!(cases.len(), 1);
+    for case in cases {
+        assert!(case.nonce.is_empty());
+        let mut drbg = CtrDrbgCore::new(&case.entropy_input, &case.personalization_string);
+        if let Some((entropy_input, additional_input)) = case.reseed {
+            drbg.reseed(&entropy_input, &additional_input);
+        }
+        let mut output = vec![0u8; case.returned_bits.len()];
+        for additional_input in case.additional_input.iter() {
+            drbg.generate(&mut output, additional_input).unwrap();
+        }
+        assert!(output == case.returned_bits, "CTR_DRBG COUNT = {}", case.count);
+    }
+}
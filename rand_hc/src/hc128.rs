@@ -11,6 +11,10 @@
 use core::fmt;
 use rand_core::block::{BlockRng, BlockRngCore};
 use rand_core::{le, CryptoRng, Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::de::{self, SeqAccess, Visitor};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 
@@ -46,6 +50,12 @@ const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 /// This implementation uses an output buffer of sixteen `u32` words, and uses
 /// [`BlockRng`] to implement the [`RngCore`] methods.
 ///
+/// The number of words consumed so far is available via `get_word_pos`. Since
+/// HC-128 is not a counter-based generator, `set_word_pos` can only seek
+/// forwards; to resume from a checkpoint, re-create the generator from the
+/// same seed (or clone an earlier copy) and fast-forward it to the recorded
+/// position.
+///
 /// ## References
 /// [^1]: Hongjun Wu (2008). ["The Stream Cipher HC-128"](
 ///       http://www.ecrypt.eu.org/stream/p3ciphers/hc/hc128_p3.pdf).
@@ -64,8 +74,56 @@ const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 /// [^5]: Internet Engineering Task Force (February 2015),
 ///       ["Prohibiting RC4 Cipher Suites"](https://tools.ietf.org/html/rfc7465).
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct Hc128Rng(BlockRng<Hc128Core>);
 
+impl Hc128Rng {
+    /// Get the offset from the start of the stream, in 32-bit words.
+    ///
+    /// This is the number of `u32` words consumed so far. Sub-word offsets are
+    /// not supported, hence the result can simply be multiplied by 4 to get a
+    /// byte-offset.
+    #[inline]
+    pub fn get_word_pos(&self) -> u64 {
+        // The core position is incremented *after* filling the buffer.
+        let buffered = (16 - self.0.index()) as u64;
+        self.0.core.word_pos - buffered
+    }
+
+    /// Fast-forward to the given offset from the start of the stream, in
+    /// 32-bit words.
+    ///
+    /// HC-128 cannot be run backwards, so `word_offset` must not be less than
+    /// [`Hc128Rng::get_word_pos`]. Skipped words are generated a block at a
+    /// time without being buffered, which is considerably faster than
+    /// discarding them one by one, but still takes time linear in the
+    /// distance.
+    ///
+    /// # Panics
+    ///
+    /// If `word_offset` is less than the current position.
+    pub fn set_word_pos(&mut self, word_offset: u64) {
+        let pos = self.get_word_pos();
+        assert!(
+            word_offset >= pos,
+            "Hc128Rng::set_word_pos: cannot seek backwards"
+        );
+
+        if word_offset < self.0.core.word_pos {
+            // The target is within the current buffer.
+            for _ in pos..word_offset {
+                self.0.next_u32();
+            }
+        } else {
+            let mut skipped = [0u32; 16];
+            while self.0.core.word_pos < word_offset & !15 {
+                self.0.core.generate(&mut skipped);
+            }
+            self.0.generate_and_set((word_offset % 16) as usize);
+        }
+    }
+}
+
 impl RngCore for Hc128Rng {
     #[inline]
     fn next_u32(&mut self) -> u32 {
@@ -109,6 +167,7 @@ impl CryptoRng for Hc128Rng {}
 pub struct Hc128Core {
     t: [u32; 1024],
     counter1024: usize,
+    word_pos: u64,
 }
 
 // Custom Debug implementation that does not expose the internal state
@@ -118,6 +177,63 @@ impl fmt::Debug for Hc128Core {
     }
 }
 
+// serde only implements its traits for arrays of up to 32 elements, so we
+// (de)serialize the table followed by the two counters as a flat tuple.
+#[cfg(feature = "serde1")]
+impl Serialize for Hc128Core {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+        let mut tuple = serializer.serialize_tuple(self.t.len() + 2)?;
+        for x in self.t.iter() {
+            tuple.serialize_element(x)?;
+        }
+        tuple.serialize_element(&(self.counter1024 as u64))?;
+        tuple.serialize_element(&self.word_pos)?;
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de> Deserialize<'de> for Hc128Core {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Hc128CoreVisitor;
+
+        impl<'de> Visitor<'de> for Hc128CoreVisitor {
+            type Value = Hc128Core;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "the HC-128 state table followed by two counters")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Hc128Core, A::Error> {
+                let mut t = [0u32; 1024];
+                for (i, x) in t.iter_mut().enumerate() {
+                    *x = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                let counter1024: u64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1024, &self))?;
+                let word_pos = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1025, &self))?;
+                if counter1024 % 16 != 0 {
+                    return Err(de::Error::custom("HC-128 counter is not block aligned"));
+                }
+                Ok(Hc128Core {
+                    t,
+                    // only the position modulo 1024 is significant
+                    counter1024: (counter1024 % 1024) as usize,
+                    word_pos,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(1024 + 2, Hc128CoreVisitor)
+    }
+}
+
 impl BlockRngCore for Hc128Core {
     type Item = u32;
     type Results = [u32; 16];
@@ -173,6 +289,7 @@ impl BlockRngCore for Hc128Core {
             results[15] = self.step_q(cc+15, dd+0,  cc+12, cc+5,  cc+3);
         }
         self.counter1024 = self.counter1024.wrapping_add(16);
+        self.word_pos = self.word_pos.wrapping_add(16);
     }
 }
 
@@ -318,7 +435,7 @@ impl Hc128Core {
                 .wrapping_add(256 + i as u32);
         }
 
-        let mut core = Self { t, counter1024: 0 };
+        let mut core = Self { t, counter1024: 0, word_pos: 0 };
 
         // run the cipher 1024 steps
         for _ in 0..64 {
@@ -484,6 +601,67 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_hc128_word_pos() {
+        #[rustfmt::skip]
+        let seed = [0x55,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, // key
+                    0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0]; // iv
+        let mut rng1 = Hc128Rng::from_seed(seed);
+        assert_eq!(rng1.get_word_pos(), 0);
+        let mut rng2 = rng1.clone();
+
+        rng1.next_u32();
+        assert_eq!(rng1.get_word_pos(), 1);
+        rng1.next_u64();
+        assert_eq!(rng1.get_word_pos(), 3);
+
+        // Skip across several P and Q blocks:
+        for _ in 3..2000 {
+            rng1.next_u32();
+        }
+        assert_eq!(rng1.get_word_pos(), 2000);
+        rng2.set_word_pos(2000);
+        assert_eq!(rng2.get_word_pos(), 2000);
+        for _ in 0..100 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+
+        // Seeking within the current buffer:
+        let mut rng3 = rng2.clone();
+        let pos = rng2.get_word_pos();
+        rng2.set_word_pos(pos + 5);
+        for _ in 0..5 {
+            rng3.next_u32();
+        }
+        assert_eq!(rng2.get_word_pos(), pos + 5);
+        assert_eq!(rng2.next_u32(), rng3.next_u32());
+
+        // Seeking to the current position is a no-op:
+        let pos = rng2.get_word_pos();
+        rng2.set_word_pos(pos);
+        assert_eq!(rng2.next_u32(), rng3.next_u32());
+    }
+
+    #[test]
+    fn test_hc128_word_pos_block_aligned() {
+        let mut rng1 = Hc128Rng::from_seed([7; 32]);
+        let mut rng2 = rng1.clone();
+        for _ in 0..512 {
+            rng1.next_u32();
+        }
+        rng2.set_word_pos(512);
+        assert_eq!(rng1.get_word_pos(), rng2.get_word_pos());
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hc128_word_pos_backwards() {
+        let mut rng = Hc128Rng::from_seed([7; 32]);
+        rng.set_word_pos(100);
+        rng.set_word_pos(99);
+    }
+
     #[test]
     fn test_hc128_clone() {
         #[rustfmt::skip]
@@ -495,4 +673,21 @@ mod test {
             assert_eq!(rng1.next_u32(), rng2.next_u32());
         }
     }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_hc128_serde() {
+        let seed = [0x42u8; 32];
+        let mut rng = Hc128Rng::from_seed(seed);
+        for _ in 0..1000 {
+            rng.next_u32();
+        }
+
+        let encoded = bincode::serialize(&rng).expect("Could not serialize");
+        let mut decoded: Hc128Rng = bincode::deserialize(&encoded).expect("Could not deserialize");
+        assert_eq!(rng.get_word_pos(), decoded.get_word_pos());
+        for _ in 0..2000 {
+            assert_eq!(rng.next_u32(), decoded.next_u32());
+        }
+    }
 }
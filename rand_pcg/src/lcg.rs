@@ -0,0 +1,109 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Jump-ahead and distance functions for the LCGs underlying PCG
+
+use core::ops::{BitAnd, BitOr, Shl, Shr};
+
+/// The state of an LCG: an unsigned integer with wrapping arithmetic.
+pub(crate) trait LcgState:
+    Copy
+    + PartialEq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_mul(self, other: Self) -> Self;
+}
+
+macro_rules! lcg_state_impl {
+    ($ty:ty) => {
+        impl LcgState for $ty {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline(always)]
+            fn wrapping_add(self, other: Self) -> Self {
+                <$ty>::wrapping_add(self, other)
+            }
+
+            #[inline(always)]
+            fn wrapping_mul(self, other: Self) -> Self {
+                <$ty>::wrapping_mul(self, other)
+            }
+        }
+    };
+}
+
+lcg_state_impl! { u64 }
+#[cfg(not(target_os = "emscripten"))]
+lcg_state_impl! { u128 }
+
+/// Compute the multiplier and increment equivalent to `delta` LCG steps.
+///
+/// Returns `(mult, plus)` such that advancing a state `s` by `delta` steps
+/// yields `mult * s + plus` (modulo 2<sup>N</sup>, with `N` the number of
+/// state bits).
+#[inline]
+pub(crate) fn lcg_jump<T: LcgState>(multiplier: T, increment: T, delta: T) -> (T, T) {
+    let mut acc_mult = T::ONE;
+    let mut acc_plus = T::ZERO;
+    let mut cur_mult = multiplier;
+    let mut cur_plus = increment;
+    let mut mdelta = delta;
+
+    while mdelta != T::ZERO {
+        if (mdelta & T::ONE) != T::ZERO {
+            acc_mult = acc_mult.wrapping_mul(cur_mult);
+            acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+        }
+        cur_plus = cur_mult.wrapping_add(T::ONE).wrapping_mul(cur_plus);
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+        mdelta = mdelta >> 1;
+    }
+    (acc_mult, acc_plus)
+}
+
+/// Compute the number of LCG steps from `state` to `target`, or `None` if
+/// `target` cannot be reached.
+///
+/// `the_bit` is the lowest state bit which changes with each step; the result
+/// is scaled accordingly, and the bits below it must already be equal. As in
+/// the reference PCG implementation, the bits are matched from the lowest:
+/// each power-of-two number of steps changes one more bit and leaves the
+/// lower ones unchanged, given PCG's multipliers and an odd increment (or an
+/// odd state for an MCG). If a step does not match its bit, no larger step
+/// can, so `target` is not reachable; hence the loop ends after matching the
+/// highest bit at the latest, whatever the arguments.
+pub(crate) fn lcg_distance<T: LcgState>(
+    multiplier: T, increment: T, state: T, target: T, mut the_bit: T,
+) -> Option<T> {
+    let mut cur_state = state;
+    let mut cur_mult = multiplier;
+    let mut cur_plus = increment;
+    let mut distance = T::ZERO;
+
+    while cur_state != target {
+        if (cur_state & the_bit) != (target & the_bit) {
+            cur_state = cur_state.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            distance = distance | the_bit;
+            if (cur_state & the_bit) != (target & the_bit) {
+                return None;
+            }
+        }
+        the_bit = the_bit << 1;
+        cur_plus = cur_mult.wrapping_add(T::ONE).wrapping_mul(cur_plus);
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+    }
+    Some(distance)
+}
@@ -23,8 +23,11 @@
 //!     a general purpose RNG using 128-bit multiplications. This has poor
 //!     performance on 32-bit CPUs but is a good choice on 64-bit CPUs for
 //!     both 32-bit and 64-bit output.
+//! -   `Pcg64Dxsm` aka `Lcg128CmDxsm64`, known as `pcg64_dxsm` (or `PCG64DXSM`
+//!     in NumPy), a general purpose RNG with a stronger output function than
+//!     `Pcg64`. This is a good choice on 64-bit CPUs.
 //!
-//! Both of these use 16 bytes of state and 128-bit seeds, and are considered
+//! These generators are considered
 //! value-stable (i.e. any change affecting the output given a fixed seed would
 //! be considered a breaking change to the crate).
 
@@ -38,9 +41,13 @@
 #![allow(clippy::unreadable_literal)]
 #![no_std]
 
+mod lcg;
 #[cfg(not(target_os = "emscripten"))] mod pcg128;
+#[cfg(not(target_os = "emscripten"))] mod pcg128cm;
 mod pcg64;
 
 #[cfg(not(target_os = "emscripten"))]
 pub use self::pcg128::{Lcg128Xsl64, Mcg128Xsl64, Pcg64, Pcg64Mcg};
+#[cfg(not(target_os = "emscripten"))]
+pub use self::pcg128cm::{Lcg128CmDxsm64, Pcg64Dxsm};
 pub use self::pcg64::{Lcg64Xsh32, Pcg32};
@@ -13,6 +13,7 @@
 // This is the default multiplier used by PCG for 128-bit state.
 const MULTIPLIER: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645;
 
+use crate::lcg::{lcg_distance, lcg_jump};
 use core::fmt;
 use rand_core::{le, Error, RngCore, SeedableRng};
 #[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
@@ -52,6 +53,48 @@ impl Lcg128Xsl64 {
         Lcg128Xsl64::from_state_incr(state, increment)
     }
 
+    /// Multi-step advance function (jump-ahead).
+    ///
+    /// The method used here is based on Brown, "Random Number Generation
+    /// with Arbitrary Stride", Transactions of the American Nuclear
+    /// Society (Nov. 1994). The algorithm is very similar to fast
+    /// exponentiation and runs in `O(log delta)` time.
+    ///
+    /// Using this function is equivalent to calling `next_u64()` `delta`
+    /// number of times.
+    #[inline]
+    pub fn advance(&mut self, delta: u128) {
+        let (acc_mult, acc_plus) = lcg_jump(MULTIPLIER, self.increment, delta);
+        self.state = acc_mult.wrapping_mul(self.state).wrapping_add(acc_plus);
+    }
+
+    /// Multi-step retreat function (jump-back).
+    ///
+    /// This undoes `delta` steps, such that calling `backstep(delta)` after
+    /// `advance(delta)` restores the original state. Since the generator has
+    /// period 2<sup>128</sup>, this is implemented as advancing "the long way
+    /// round" and has the same cost as [`Lcg128Xsl64::advance`].
+    #[inline]
+    pub fn backstep(&mut self, delta: u128) {
+        self.advance(delta.wrapping_neg())
+    }
+
+    /// Number of steps from `self` to `other`.
+    ///
+    /// Returns `Some(delta)` such that `self.advance(delta)` yields a
+    /// generator in the same state as `other`, or `None` if `other` can never
+    /// be reached from `self` (e.g. the two generators do not share a stream).
+    /// `None` is also returned for an even increment, which only
+    /// deserialization can give.
+    ///
+    /// This is the inverse of [`Lcg128Xsl64::advance`].
+    pub fn distance(&self, other: &Self) -> Option<u128> {
+        if self.increment != other.increment || (self.increment & 1) == 0 {
+            return None;
+        }
+        lcg_distance(MULTIPLIER, self.increment, self.state, other.state, 1)
+    }
+
     #[inline]
     fn from_state_incr(state: u128, increment: u128) -> Self {
         let mut pcg = Lcg128Xsl64 { state, increment };
@@ -149,6 +192,50 @@ impl Mcg128Xsl64 {
         // Force low bit to 1, as in C version (C++ uses `state | 3` instead).
         Mcg128Xsl64 { state: state | 1 }
     }
+
+    /// Multi-step advance function (jump-ahead).
+    ///
+    /// See [`Lcg128Xsl64::advance`]; this is the same algorithm without the
+    /// additive constant.
+    ///
+    /// Using this function is equivalent to calling `next_u64()` `delta`
+    /// number of times.
+    #[inline]
+    pub fn advance(&mut self, delta: u128) {
+        let (acc_mult, _) = lcg_jump(MULTIPLIER, 0, delta);
+        self.state = acc_mult.wrapping_mul(self.state);
+    }
+
+    /// Multi-step retreat function (jump-back).
+    ///
+    /// This undoes `delta` steps, such that calling `backstep(delta)` after
+    /// `advance(delta)` restores the original state. The multiplier's order
+    /// divides 2<sup>128</sup>, hence this is implemented as advancing "the
+    /// long way round".
+    #[inline]
+    pub fn backstep(&mut self, delta: u128) {
+        self.advance(delta.wrapping_neg())
+    }
+
+    /// Number of steps from `self` to `other`.
+    ///
+    /// Returns `Some(delta)` such that `self.advance(delta)` yields a
+    /// generator in the same state as `other`, or `None` if `other` can never
+    /// be reached from `self`. The constructors force the state to be odd,
+    /// and the odd states form two disjoint cycles of period 2<sup>126</sup>,
+    /// distinguished by the second-lowest state bit; `None` is returned when
+    /// the two lowest state bits of `self` and `other` differ. `None` is also
+    /// returned for an even state, which only deserialization can give.
+    ///
+    /// This is the inverse of [`Mcg128Xsl64::advance`].
+    pub fn distance(&self, other: &Self) -> Option<u128> {
+        if (self.state & 3) != (other.state & 3) || (self.state & 1) == 0 {
+            return None;
+        }
+        // With a multiplier of 5 mod 8, bit 2 is the lowest bit to change
+        // with each step.
+        lcg_distance(MULTIPLIER, 0, self.state, other.state, 4).map(|delta| delta >> 2)
+    }
 }
 
 // Custom Debug implementation that does not expose the internal state
@@ -210,7 +297,7 @@ fn output_xsl_rr(state: u128) -> u64 {
 }
 
 #[inline(always)]
-fn fill_bytes_impl<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
+pub(crate) fn fill_bytes_impl<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
     let mut left = dest;
     while left.len() >= 8 {
         let (l, r) = { left }.split_at_mut(8);
@@ -0,0 +1,186 @@
+// Copyright 2018-2020 Developers of the Rand project.
+// Copyright 2017 Paul Dicker.
+// Copyright 2014-2017, 2019 Melissa O'Neill and PCG Project contributors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! PCG random number generators
+
+// This is the cheap multiplier used by PCG for 128-bit state.
+const MULTIPLIER: u64 = 15750249268501108917;
+
+use crate::lcg::{lcg_distance, lcg_jump};
+use crate::pcg128::fill_bytes_impl;
+use core::fmt;
+use rand_core::{le, Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
+
+/// A PCG random number generator (CM DXSM 128/64 (LCG) variant).
+///
+/// Permuted Congruential Generator with 128-bit state, internal Linear
+/// Congruential Generator, and 64-bit output via "double xorshift multiply"
+/// output function.
+///
+/// This is a 128-bit LCG with explicitly chosen stream with the PCG-DXSM
+/// output function. This corresponds to `pcg_engines::cm_setseq_dxsm_128_64`
+/// from pcg_cpp and `PCG64DXSM` from NumPy.
+///
+/// Despite the name, this implementation uses 32 bytes (256 bit) space
+/// comprising 128 bits of state and 128 bits stream selector. These are both
+/// set by `SeedableRng`, using a 256-bit seed.
+///
+/// Note that while this generator is only mentioned in the PCG paper's
+/// addendum, it has better statistical properties than the standard `pcg64`
+/// ([`Lcg128Xsl64`](crate::Lcg128Xsl64)) due to its stronger output
+/// permutation, at the cost of slightly lower performance. The LCG uses a
+/// "cheap" 64-bit multiplier.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Lcg128CmDxsm64 {
+    state: u128,
+    increment: u128,
+}
+
+/// [`Lcg128CmDxsm64`] is also known as `pcg64_dxsm`.
+pub type Pcg64Dxsm = Lcg128CmDxsm64;
+
+impl Lcg128CmDxsm64 {
+    /// Multi-step advance function (jump-ahead).
+    ///
+    /// See [`Lcg128Xsl64::advance`](crate::Lcg128Xsl64::advance).
+    ///
+    /// Using this function is equivalent to calling `next_u64()` `delta`
+    /// number of times.
+    #[inline]
+    pub fn advance(&mut self, delta: u128) {
+        let (acc_mult, acc_plus) = lcg_jump(MULTIPLIER as u128, self.increment, delta);
+        self.state = acc_mult.wrapping_mul(self.state).wrapping_add(acc_plus);
+    }
+
+    /// Multi-step retreat function (jump-back).
+    ///
+    /// This undoes `delta` steps, such that calling `backstep(delta)` after
+    /// `advance(delta)` restores the original state.
+    #[inline]
+    pub fn backstep(&mut self, delta: u128) {
+        self.advance(delta.wrapping_neg())
+    }
+
+    /// Number of steps from `self` to `other`.
+    ///
+    /// Returns `Some(delta)` such that `self.advance(delta)` yields a
+    /// generator in the same state as `other`, or `None` if `other` can never
+    /// be reached from `self` (e.g. the two generators do not share a stream).
+    /// `None` is also returned for an even increment, which only
+    /// deserialization can give.
+    pub fn distance(&self, other: &Self) -> Option<u128> {
+        if self.increment != other.increment || (self.increment & 1) == 0 {
+            return None;
+        }
+        lcg_distance(MULTIPLIER as u128, self.increment, self.state, other.state, 1)
+    }
+
+    /// Construct an instance compatible with PCG seed and stream.
+    ///
+    /// Note that the state and stream are interpreted exactly as by
+    /// `pcg_cm_srandom_r` in NumPy's `PCG64DXSM`.
+    ///
+    /// PCG specifies the following default values for both parameters:
+    ///
+    /// - `state = 0xcafef00dd15ea5e5`
+    /// - `stream = 0xa02bdbf7bb3c0a7ac28fa16a64abf96`
+    pub fn new(state: u128, stream: u128) -> Self {
+        // The increment must be odd, hence we discard one bit:
+        let increment = (stream << 1) | 1;
+        Self::from_state_incr(state, increment)
+    }
+
+    #[inline]
+    fn from_state_incr(state: u128, increment: u128) -> Self {
+        let mut pcg = Self { state, increment };
+        // Move away from inital value:
+        pcg.state = pcg.state.wrapping_add(pcg.increment);
+        pcg.step();
+        pcg
+    }
+
+    #[inline(always)]
+    fn step(&mut self) {
+        // prepare the LCG for the next round
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER as u128)
+            .wrapping_add(self.increment);
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Lcg128CmDxsm64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lcg128CmDxsm64 {{}}")
+    }
+}
+
+/// We use a single 255-bit seed to initialise the state and select a stream.
+/// One `seed` bit (lowest bit of `seed[16]`) is ignored.
+impl SeedableRng for Lcg128CmDxsm64 {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 4];
+        le::read_u64_into(&seed, &mut seed_u64);
+        let state = u128::from(seed_u64[0]) | (u128::from(seed_u64[1]) << 64);
+        let incr = u128::from(seed_u64[2]) | (u128::from(seed_u64[3]) << 64);
+
+        // The increment must be odd, hence we discard one bit:
+        Self::from_state_incr(state, incr | 1)
+    }
+}
+
+impl RngCore for Lcg128CmDxsm64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        // Unlike the other PCG variants, DXSM outputs the state from before
+        // the LCG step, which allows computing both in parallel.
+        let res = output_dxsm(self.state);
+        self.step();
+        res
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_impl(self, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[inline(always)]
+fn output_dxsm(state: u128) -> u64 {
+    // Output function DXSM ("double xorshift multiply")
+    // See https://github.com/imneme/pcg-cpp/blob/ffd522e7188bef30a00c74dc7eb9de5faff90092/include/pcg_random.hpp#L1016
+    // for a short discussion of the construction.
+    let mut hi = (state >> 64) as u64;
+    let mut lo = state as u64;
+
+    lo |= 1;
+    hi ^= hi >> 32;
+    hi = hi.wrapping_mul(MULTIPLIER);
+    hi ^= hi >> 48;
+    hi = hi.wrapping_mul(lo);
+
+    hi
+}
@@ -10,6 +10,7 @@
 
 //! PCG random number generators
 
+use crate::lcg::{lcg_distance, lcg_jump};
 use core::fmt;
 use rand_core::{impls, le, Error, RngCore, SeedableRng};
 #[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
@@ -53,6 +54,49 @@ impl Lcg64Xsh32 {
         Lcg64Xsh32::from_state_incr(state, increment)
     }
 
+    /// Multi-step advance function (jump-ahead).
+    ///
+    /// The method used here is based on Brown, "Random Number Generation
+    /// with Arbitrary Stride", Transactions of the American Nuclear
+    /// Society (Nov. 1994). The algorithm is very similar to fast
+    /// exponentiation and runs in `O(log delta)` time.
+    ///
+    /// Using this function is equivalent to calling `next_u32()` `delta`
+    /// number of times. Note that `next_u64()` consumes two steps.
+    #[inline]
+    pub fn advance(&mut self, delta: u64) {
+        let (acc_mult, acc_plus) = lcg_jump(MULTIPLIER, self.increment, delta);
+        self.state = acc_mult.wrapping_mul(self.state).wrapping_add(acc_plus);
+    }
+
+    /// Multi-step retreat function (jump-back).
+    ///
+    /// This undoes `delta` steps, such that calling `backstep(delta)` after
+    /// `advance(delta)` restores the original state. Since the generator has
+    /// period 2<sup>64</sup>, this is implemented as advancing "the long way
+    /// round" and has the same cost as [`Lcg64Xsh32::advance`].
+    #[inline]
+    pub fn backstep(&mut self, delta: u64) {
+        self.advance(delta.wrapping_neg())
+    }
+
+    /// Number of steps from `self` to `other`.
+    ///
+    /// Returns `Some(delta)` such that `self.advance(delta)` yields a
+    /// generator in the same state as `other`, or `None` if `other` can never
+    /// be reached from `self` (e.g. the two generators do not share a stream).
+    /// `None` is also returned for an even increment, which only
+    /// deserialization can give.
+    ///
+    /// This is the inverse of [`Lcg64Xsh32::advance`] and uses the same
+    /// `O(log delta)` bit-by-bit approach as the reference PCG implementation.
+    pub fn distance(&self, other: &Self) -> Option<u64> {
+        if self.increment != other.increment || (self.increment & 1) == 0 {
+            return None;
+        }
+        lcg_distance(MULTIPLIER, self.increment, self.state, other.state, 1)
+    }
+
     #[inline]
     fn from_state_incr(state: u64, increment: u64) -> Self {
         let mut pcg = Lcg64Xsh32 { state, increment };
@@ -41,6 +41,79 @@ fn test_lcg128xsl64_true_values() {
     assert_eq!(results, expected);
 }
 
+#[test]
+fn test_lcg128xsl64_advancing() {
+    for seed in 0..20 {
+        let mut rng1 = Lcg128Xsl64::seed_from_u64(seed);
+        let mut rng2 = rng1.clone();
+        for _ in 0..20 {
+            rng1.next_u64();
+        }
+        rng2.advance(20);
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+}
+
+#[test]
+fn test_lcg128xsl64_backstepping() {
+    let mut rng = Lcg128Xsl64::seed_from_u64(42);
+    let mut results = [0u64; 16];
+    for i in results.iter_mut() {
+        *i = rng.next_u64();
+    }
+    rng.backstep(16);
+    for &x in results.iter() {
+        assert_eq!(rng.next_u64(), x);
+    }
+
+    // Advancing past zero and stepping back must round-trip:
+    let mut rng1 = Lcg128Xsl64::seed_from_u64(7);
+    let mut rng2 = rng1.clone();
+    rng2.advance(!0);
+    rng2.backstep(!0);
+    assert_eq!(rng1.next_u64(), rng2.next_u64());
+}
+
+#[test]
+fn test_lcg128xsl64_distance() {
+    let rng1 = Lcg128Xsl64::seed_from_u64(1);
+    assert_eq!(rng1.distance(&rng1), Some(0));
+
+    for &delta in [1, 2, 20, 12345, !0 >> 1, !0].iter() {
+        let mut rng2 = rng1.clone();
+        rng2.advance(delta);
+        assert_eq!(rng1.distance(&rng2), Some(delta));
+        // Going the other way wraps around the period:
+        assert_eq!(rng2.distance(&rng1), Some(delta.wrapping_neg()));
+    }
+
+    let mut rng3 = rng1.clone();
+    for _ in 0..100 {
+        rng3.next_u64();
+    }
+    assert_eq!(rng1.distance(&rng3), Some(100));
+
+    // A generator which cannot be reached:
+    let rng4 = Lcg128Xsl64::new(42, 55);
+    let rng5 = Lcg128Xsl64::new(42, 54);
+    assert_eq!(rng5.distance(&rng4), None);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_lcg128xsl64_distance_even_increment() {
+    // Deserialization accepts an even increment, which is rejected.
+    let mut bytes = bincode::serialize(&Lcg128Xsl64::seed_from_u64(1)).unwrap();
+    for (i, byte) in bytes[16..].iter_mut().enumerate() {
+        *byte = if i == 0 { 2 } else { 0 };
+    }
+    let rng1: Lcg128Xsl64 = bincode::deserialize(&bytes).unwrap();
+    let mut rng2 = rng1.clone();
+    rng2.advance(12345);
+    assert_eq!(rng1.distance(&rng2), None);
+    assert_eq!(rng2.distance(&rng1), None);
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn test_lcg128xsl64_serde() {
@@ -34,6 +34,79 @@ fn test_lcg64xsh32_true_values() {
     assert_eq!(results, expected);
 }
 
+#[test]
+fn test_lcg64xsh32_advancing() {
+    for seed in 0..20 {
+        let mut rng1 = Lcg64Xsh32::seed_from_u64(seed);
+        let mut rng2 = rng1.clone();
+        for _ in 0..20 {
+            rng1.next_u32();
+        }
+        rng2.advance(20);
+        assert_eq!(rng1.next_u32(), rng2.next_u32());
+    }
+}
+
+#[test]
+fn test_lcg64xsh32_backstepping() {
+    let mut rng = Lcg64Xsh32::seed_from_u64(42);
+    let mut results = [0u32; 16];
+    for i in results.iter_mut() {
+        *i = rng.next_u32();
+    }
+    rng.backstep(16);
+    for &x in results.iter() {
+        assert_eq!(rng.next_u32(), x);
+    }
+
+    // Advancing past zero and stepping back must round-trip:
+    let mut rng1 = Lcg64Xsh32::seed_from_u64(7);
+    let mut rng2 = rng1.clone();
+    rng2.advance(!0);
+    rng2.backstep(!0);
+    assert_eq!(rng1.next_u32(), rng2.next_u32());
+}
+
+#[test]
+fn test_lcg64xsh32_distance() {
+    let rng1 = Lcg64Xsh32::seed_from_u64(1);
+    assert_eq!(rng1.distance(&rng1), Some(0));
+
+    for &delta in [1, 2, 20, 12345, !0 >> 1, !0].iter() {
+        let mut rng2 = rng1.clone();
+        rng2.advance(delta);
+        assert_eq!(rng1.distance(&rng2), Some(delta));
+        // Going the other way wraps around the period:
+        assert_eq!(rng2.distance(&rng1), Some(delta.wrapping_neg()));
+    }
+
+    let mut rng3 = rng1.clone();
+    for _ in 0..100 {
+        rng3.next_u32();
+    }
+    assert_eq!(rng1.distance(&rng3), Some(100));
+
+    // A generator which cannot be reached:
+    let rng4 = Lcg64Xsh32::new(42, 55);
+    let rng5 = Lcg64Xsh32::new(42, 54);
+    assert_eq!(rng5.distance(&rng4), None);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_lcg64xsh32_distance_even_increment() {
+    // Deserialization accepts an even increment, which is rejected.
+    let mut bytes = bincode::serialize(&Lcg64Xsh32::seed_from_u64(1)).unwrap();
+    for (i, byte) in bytes[8..].iter_mut().enumerate() {
+        *byte = if i == 0 { 2 } else { 0 };
+    }
+    let rng1: Lcg64Xsh32 = bincode::deserialize(&bytes).unwrap();
+    let mut rng2 = rng1.clone();
+    rng2.advance(12345);
+    assert_eq!(rng1.distance(&rng2), None);
+    assert_eq!(rng2.distance(&rng1), None);
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn test_lcg64xsh32_serde() {
@@ -0,0 +1,51 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Counter-based random number generators from the Random123 suite.
+//!
+//! The output of a counter-based generator is a pure function of a key and a
+//! counter: each block of output is the result of applying a keyed bijection
+//! to the counter. This allows constant-time seeking to any position in the
+//! stream, and makes it easy to reproduce the results of other
+//! implementations (e.g. on a GPU).
+//!
+//! This crate provides:
+//!
+//! -   `Philox4x32Rng`, the Philox4x32-10 generator with a 64-bit key,
+//!     producing four `u32` words per block. The block function is available
+//!     as [`philox4x32`].
+//! -   `Threefry4x64Rng`, the Threefry4x64-20 generator with a 256-bit key,
+//!     producing four `u64` words per block. The block function is available
+//!     as [`threefry4x64`].
+//!
+//! Both are implemented on top of [`rand_core::block::BlockRngCore`], and,
+//! like `ChaChaRng`, support seeking via `set_word_pos` and selecting one of
+//! 2<sup>64</sup> streams per key via `set_stream`.
+//!
+//! These generators are not suitable for cryptographic uses. They are
+//! considered value-stable (i.e. any change affecting the output given a
+//! fixed seed would be considered a breaking change to the crate).
+
+#![doc(
+    html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+    html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+    html_root_url = "https://rust-random.github.io/rand/"
+)]
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+#![doc(test(attr(allow(unused_variables), deny(warnings))))]
+#![allow(clippy::unreadable_literal)]
+#![no_std]
+
+pub use rand_core;
+
+mod philox;
+mod threefry;
+
+pub use crate::philox::{philox4x32, Philox4x32Core, Philox4x32Rng};
+pub use crate::threefry::{threefry4x64, Threefry4x64Core, Threefry4x64Rng};
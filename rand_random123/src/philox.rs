@@ -0,0 +1,317 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Philox4x32 random number generator.
+
+use core::fmt;
+use rand_core::block::{BlockRng, BlockRngCore};
+use rand_core::{le, Error, RngCore, SeedableRng};
+
+// Multipliers and Weyl sequence constants from the Random123 reference.
+const PHILOX_M4X32_0: u32 = 0xD2511F53;
+const PHILOX_M4X32_1: u32 = 0xCD9E8D57;
+const PHILOX_W32_0: u32 = 0x9E3779B9;
+const PHILOX_W32_1: u32 = 0xBB67AE85;
+
+const ROUNDS: usize = 10;
+const BLOCK_WORDS: usize = 4;
+
+#[inline(always)]
+fn mulhilo(a: u32, b: u32) -> (u32, u32) {
+    let product = u64::from(a) * u64::from(b);
+    ((product >> 32) as u32, product as u32)
+}
+
+#[inline(always)]
+fn round(ctr: [u32; 4], key: [u32; 2]) -> [u32; 4] {
+    let (hi0, lo0) = mulhilo(PHILOX_M4X32_0, ctr[0]);
+    let (hi1, lo1) = mulhilo(PHILOX_M4X32_1, ctr[2]);
+    [hi1 ^ ctr[1] ^ key[0], lo1, hi0 ^ ctr[3] ^ key[1], lo0]
+}
+
+/// The Philox4x32-10 block function.
+///
+/// Maps a 128-bit `counter` and a 64-bit `key` to 128 bits of output. This is
+/// a bijection on `counter` for each `key`, and matches `philox4x32` from the
+/// Random123 reference implementation with the default of 10 rounds.
+#[inline]
+pub fn philox4x32(counter: [u32; 4], key: [u32; 2]) -> [u32; 4] {
+    let mut ctr = counter;
+    let mut key = key;
+    for i in 0..ROUNDS {
+        if i > 0 {
+            key[0] = key[0].wrapping_add(PHILOX_W32_0);
+            key[1] = key[1].wrapping_add(PHILOX_W32_1);
+        }
+        ctr = round(ctr, key);
+    }
+    ctr
+}
+
+/// The Philox4x32-10 core, producing one block of four `u32` words per call
+/// to `generate`.
+///
+/// The 128-bit counter passed to [`philox4x32`] is made up of a 64-bit block
+/// counter (the low two words) and a 64-bit stream identifier (the high two
+/// words). Both start at zero.
+///
+/// Use [`Philox4x32Rng`] for an [`RngCore`] implementation with seeking.
+#[derive(Clone)]
+pub struct Philox4x32Core {
+    key: [u32; 2],
+    counter: u64,
+    stream: u64,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Philox4x32Core {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Philox4x32Core {{}}")
+    }
+}
+
+impl BlockRngCore for Philox4x32Core {
+    type Item = u32;
+    type Results = [u32; BLOCK_WORDS];
+
+    #[inline]
+    fn generate(&mut self, results: &mut Self::Results) {
+        let ctr = [
+            self.counter as u32,
+            (self.counter >> 32) as u32,
+            self.stream as u32,
+            (self.stream >> 32) as u32,
+        ];
+        *results = philox4x32(ctr, self.key);
+        self.counter = self.counter.wrapping_add(1);
+    }
+}
+
+/// The 64-bit seed is used as the key, read as two little-endian `u32` words.
+impl SeedableRng for Philox4x32Core {
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut key = [0u32; 2];
+        le::read_u32_into(&seed, &mut key);
+        Philox4x32Core { key, counter: 0, stream: 0 }
+    }
+}
+
+/// A counter-based random number generator using the Philox4x32-10 algorithm.
+///
+/// Philox is a counter-based generator by Salmon et al.[^1], built from
+/// rounds of 32-bit widening multiplications. It passes the TestU01 BigCrush
+/// test suite, but is not suitable for cryptographic purposes.
+///
+/// The output is a pure function of the key (the seed) and a 128-bit
+/// counter, which we split into a 64-bit block counter and a 64-bit stream
+/// identifier. A 64-bit counter over 4-word blocks allows 2<sup>68</sup>
+/// bytes of output before cycling, and the stream identifier allows
+/// 2<sup>64</sup> unique streams of output per seed. Both counter and stream
+/// are initialized to zero but may be set via the `set_word_pos` and
+/// `set_stream` methods.
+///
+/// The counter layout (as passed to [`philox4x32`]) is:
+///
+/// ```text
+/// counter   counter   stream_id stream_id
+/// ```
+///
+/// This implementation uses an output buffer of four `u32` words, and uses
+/// [`BlockRng`] to implement the [`RngCore`] methods.
+///
+/// [^1]: J. K. Salmon, M. A. Moraes, R. O. Dror and D. E. Shaw (2011).
+///       ["Parallel Random Numbers: As Easy as 1, 2, 3"](
+///       https://www.thesalmons.org/john/random123/papers/random123sc11.pdf).
+#[derive(Clone, Debug)]
+pub struct Philox4x32Rng {
+    rng: BlockRng<Philox4x32Core>,
+}
+
+impl Philox4x32Rng {
+    /// Get the offset from the start of the stream, in 32-bit words.
+    ///
+    /// Since the generated blocks are 4 words (2<sup>2</sup>) long and the
+    /// counter is 64-bits, the offset is a 66-bit number. Sub-word offsets are
+    /// not supported, hence the result can simply be multiplied by 4 to get a
+    /// byte-offset.
+    #[inline]
+    pub fn get_word_pos(&self) -> u128 {
+        // The core counter is incremented *after* filling the buffer.
+        let block = u128::from(self.rng.core.counter);
+        let buffered = (BLOCK_WORDS - self.rng.index()) as u128;
+        ((block << 2).wrapping_sub(buffered)) & ((1 << 66) - 1)
+    }
+
+    /// Set the offset from the start of the stream, in 32-bit words.
+    ///
+    /// As with `get_word_pos`, we use a 66-bit number. Since the generator
+    /// simply cycles at the end of its period, we ignore the upper 62 bits.
+    #[inline]
+    pub fn set_word_pos(&mut self, word_offset: u128) {
+        self.rng.core.counter = (word_offset >> 2) as u64;
+        self.rng.generate_and_set((word_offset & 3) as usize);
+    }
+
+    /// Set the stream number.
+    ///
+    /// This is initialized to zero; 2<sup>64</sup> unique streams of output
+    /// are available per seed/key. The current word position is retained.
+    #[inline]
+    pub fn set_stream(&mut self, stream: u64) {
+        self.rng.core.stream = stream;
+        if self.rng.index() != BLOCK_WORDS {
+            let wp = self.get_word_pos();
+            self.set_word_pos(wp);
+        }
+    }
+
+    /// Get the stream number.
+    #[inline]
+    pub fn get_stream(&self) -> u64 {
+        self.rng.core.stream
+    }
+}
+
+impl SeedableRng for Philox4x32Rng {
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Philox4x32Rng {
+            rng: BlockRng::new(Philox4x32Core::from_seed(seed)),
+        }
+    }
+}
+
+impl RngCore for Philox4x32Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        self.rng.fill_bytes(bytes)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(bytes)
+    }
+}
+
+impl From<Philox4x32Core> for Philox4x32Rng {
+    fn from(core: Philox4x32Core) -> Self {
+        Philox4x32Rng {
+            rng: BlockRng::new(core),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_philox4x32_kat() {
+        // Known-answer tests from `kat_vectors` in Random123 1.09.
+        assert_eq!(philox4x32([0, 0, 0, 0], [0, 0]), [
+            0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8
+        ]);
+        assert_eq!(
+            philox4x32([0xffffffff; 4], [0xffffffff; 2]),
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+        assert_eq!(
+            philox4x32([0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344], [
+                0xa4093822, 0x299f31d0
+            ]),
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+        );
+    }
+
+    #[test]
+    fn test_philox4x32_rng_true_values() {
+        // The first block is the all-zero known answer, the next uses counter 1.
+        let mut rng = Philox4x32Rng::from_seed([0; 8]);
+        let mut results = [0u32; 8];
+        for x in results.iter_mut() {
+            *x = rng.next_u32();
+        }
+        assert_eq!(results[..4], [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]);
+        assert_eq!(results[4..], philox4x32([1, 0, 0, 0], [0, 0]));
+
+        // The seed is read as a little-endian key:
+        let seed = [0x22, 0x38, 0x09, 0xa4, 0xd0, 0x31, 0x9f, 0x29];
+        let mut rng = Philox4x32Rng::from_seed(seed);
+        rng.set_stream(0x03707344_13198a2e);
+        rng.set_word_pos(0x85a308d3_243f6a88 << 2);
+        let mut results = [0u32; 4];
+        for x in results.iter_mut() {
+            *x = rng.next_u32();
+        }
+        assert_eq!(results, [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]);
+    }
+
+    #[test]
+    fn test_philox4x32_word_pos() {
+        let mut rng1 = Philox4x32Rng::seed_from_u64(42);
+        assert_eq!(rng1.get_word_pos(), 0);
+        let mut rng2 = rng1.clone();
+        for _ in 0..7 {
+            rng1.next_u32();
+        }
+        assert_eq!(rng1.get_word_pos(), 7);
+
+        rng2.set_word_pos(7);
+        assert_eq!(rng2.get_word_pos(), 7);
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+
+        // Seeking backwards:
+        rng2.set_word_pos(3);
+        let mut rng3 = Philox4x32Rng::seed_from_u64(42);
+        for _ in 0..3 {
+            rng3.next_u32();
+        }
+        assert_eq!(rng2.next_u64(), rng3.next_u64());
+
+        // The position wraps at 2^66 words:
+        rng2.set_word_pos((1 << 66) - 1);
+        assert_eq!(rng2.get_word_pos(), (1 << 66) - 1);
+        rng2.next_u32();
+        assert_eq!(rng2.get_word_pos(), 0);
+    }
+
+    #[test]
+    fn test_philox4x32_streams() {
+        let mut rng1 = Philox4x32Rng::seed_from_u64(0);
+        let mut rng2 = rng1.clone();
+        rng1.next_u32();
+        rng2.next_u32();
+        rng2.set_stream(1);
+        assert_eq!(rng2.get_stream(), 1);
+        assert_eq!(rng2.get_word_pos(), 1);
+        assert_ne!(rng1.next_u32(), rng2.next_u32());
+
+        // Streams are independent of the order of calls:
+        let mut rng3 = Philox4x32Rng::seed_from_u64(0);
+        rng3.set_stream(1);
+        rng3.set_word_pos(2);
+        assert_eq!(rng2.next_u64(), rng3.next_u64());
+    }
+}
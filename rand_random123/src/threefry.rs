@@ -0,0 +1,358 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Threefry4x64 random number generator.
+
+use core::fmt;
+use rand_core::block::{BlockRng64, BlockRngCore};
+use rand_core::{le, Error, RngCore, SeedableRng};
+
+// Rotation constants for Threefish-256, as used by the Random123 reference.
+const ROTATIONS: [[u32; 2]; 8] = [
+    [14, 16],
+    [52, 57],
+    [23, 40],
+    [5, 37],
+    [25, 33],
+    [46, 12],
+    [58, 22],
+    [32, 32],
+];
+
+const SKEIN_KS_PARITY64: u64 = 0x1BD11BDAA9FC1A22;
+
+const ROUNDS: usize = 20;
+const BLOCK_WORDS: usize = 4;
+
+fn threefry4x64_rounds(counter: [u64; 4], key: [u64; 4], rounds: usize) -> [u64; 4] {
+    let ks = [
+        key[0],
+        key[1],
+        key[2],
+        key[3],
+        SKEIN_KS_PARITY64 ^ key[0] ^ key[1] ^ key[2] ^ key[3],
+    ];
+    let mut x = [0u64; 4];
+    for i in 0..4 {
+        x[i] = counter[i].wrapping_add(ks[i]);
+    }
+
+    for r in 0..rounds {
+        let rot = ROTATIONS[r % 8];
+        if r % 2 == 0 {
+            x[0] = x[0].wrapping_add(x[1]);
+            x[1] = x[1].rotate_left(rot[0]) ^ x[0];
+            x[2] = x[2].wrapping_add(x[3]);
+            x[3] = x[3].rotate_left(rot[1]) ^ x[2];
+        } else {
+            x[0] = x[0].wrapping_add(x[3]);
+            x[3] = x[3].rotate_left(rot[0]) ^ x[0];
+            x[2] = x[2].wrapping_add(x[1]);
+            x[1] = x[1].rotate_left(rot[1]) ^ x[2];
+        }
+
+        // Inject the key schedule after every fourth round:
+        if r % 4 == 3 {
+            let s = (r + 1) / 4;
+            for i in 0..4 {
+                x[i] = x[i].wrapping_add(ks[(s + i) % 5]);
+            }
+            x[3] = x[3].wrapping_add(s as u64);
+        }
+    }
+    x
+}
+
+/// The Threefry4x64-20 block function.
+///
+/// Maps a 256-bit `counter` and a 256-bit `key` to 256 bits of output. This is
+/// a bijection on `counter` for each `key`, and matches `threefry4x64` from the
+/// Random123 reference implementation with the default of 20 rounds.
+#[inline]
+pub fn threefry4x64(counter: [u64; 4], key: [u64; 4]) -> [u64; 4] {
+    threefry4x64_rounds(counter, key, ROUNDS)
+}
+
+/// The Threefry4x64-20 core, producing one block of four `u64` words per call
+/// to `generate`.
+///
+/// The 256-bit counter passed to [`threefry4x64`] is made up of a 64-bit
+/// block counter (the first word) and a 64-bit stream identifier (the second
+/// word); the remaining words are zero. Both start at zero.
+///
+/// Use [`Threefry4x64Rng`] for an [`RngCore`] implementation with seeking.
+#[derive(Clone)]
+pub struct Threefry4x64Core {
+    key: [u64; 4],
+    counter: u64,
+    stream: u64,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Threefry4x64Core {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Threefry4x64Core {{}}")
+    }
+}
+
+impl BlockRngCore for Threefry4x64Core {
+    type Item = u64;
+    type Results = [u64; BLOCK_WORDS];
+
+    #[inline]
+    fn generate(&mut self, results: &mut Self::Results) {
+        let ctr = [self.counter, self.stream, 0, 0];
+        *results = threefry4x64(ctr, self.key);
+        self.counter = self.counter.wrapping_add(1);
+    }
+}
+
+/// The 256-bit seed is used as the key, read as four little-endian `u64`
+/// words.
+impl SeedableRng for Threefry4x64Core {
+    type Seed = [u8; 32];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut key = [0u64; 4];
+        le::read_u64_into(&seed, &mut key);
+        Threefry4x64Core { key, counter: 0, stream: 0 }
+    }
+}
+
+/// A counter-based random number generator using the Threefry4x64-20
+/// algorithm.
+///
+/// Threefry is a counter-based generator by Salmon et al.[^1], derived from
+/// the Threefish block cipher used by the Skein hash function. It uses only
+/// add-rotate-xor operations, so is fast on CPUs without fast multiplication.
+/// It passes the TestU01 BigCrush test suite, but is not suitable for
+/// cryptographic purposes.
+///
+/// The output is a pure function of the key (the seed) and a 256-bit
+/// counter, of which we use a 64-bit block counter and a 64-bit stream
+/// identifier. A 64-bit counter over 4-word blocks allows 2<sup>69</sup>
+/// bytes of output before cycling, and the stream identifier allows
+/// 2<sup>64</sup> unique streams of output per seed. Both counter and stream
+/// are initialized to zero but may be set via the `set_word_pos` and
+/// `set_stream` methods.
+///
+/// The counter layout (as passed to [`threefry4x64`]) is:
+///
+/// ```text
+/// counter   stream_id 0         0
+/// ```
+///
+/// This implementation uses an output buffer of four `u64` words, and uses
+/// [`BlockRng64`] to implement the [`RngCore`] methods.
+///
+/// [^1]: J. K. Salmon, M. A. Moraes, R. O. Dror and D. E. Shaw (2011).
+///       ["Parallel Random Numbers: As Easy as 1, 2, 3"](
+///       https://www.thesalmons.org/john/random123/papers/random123sc11.pdf).
+#[derive(Clone, Debug)]
+pub struct Threefry4x64Rng {
+    rng: BlockRng64<Threefry4x64Core>,
+}
+
+impl Threefry4x64Rng {
+    /// Get the offset from the start of the stream, in 64-bit words.
+    ///
+    /// Since the generated blocks are 4 words (2<sup>2</sup>) long and the
+    /// counter is 64-bits, the offset is a 66-bit number. Sub-word offsets are
+    /// not supported, hence the result can simply be multiplied by 8 to get a
+    /// byte-offset. If half of a word has been consumed by `next_u32`, that
+    /// word is counted as consumed.
+    #[inline]
+    pub fn get_word_pos(&self) -> u128 {
+        // The core counter is incremented *after* filling the buffer.
+        let block = u128::from(self.rng.core.counter);
+        let buffered = (BLOCK_WORDS - self.rng.index()) as u128;
+        ((block << 2).wrapping_sub(buffered)) & ((1 << 66) - 1)
+    }
+
+    /// Set the offset from the start of the stream, in 64-bit words.
+    ///
+    /// As with `get_word_pos`, we use a 66-bit number. Since the generator
+    /// simply cycles at the end of its period, we ignore the upper 62 bits.
+    #[inline]
+    pub fn set_word_pos(&mut self, word_offset: u128) {
+        self.rng.core.counter = (word_offset >> 2) as u64;
+        self.rng.generate_and_set((word_offset & 3) as usize);
+    }
+
+    /// Set the stream number.
+    ///
+    /// This is initialized to zero; 2<sup>64</sup> unique streams of output
+    /// are available per seed/key. The current word position is retained.
+    #[inline]
+    pub fn set_stream(&mut self, stream: u64) {
+        self.rng.core.stream = stream;
+        if self.rng.index() != BLOCK_WORDS {
+            let wp = self.get_word_pos();
+            self.set_word_pos(wp);
+        }
+    }
+
+    /// Get the stream number.
+    #[inline]
+    pub fn get_stream(&self) -> u64 {
+        self.rng.core.stream
+    }
+}
+
+impl SeedableRng for Threefry4x64Rng {
+    type Seed = [u8; 32];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Threefry4x64Rng {
+            rng: BlockRng64::new(Threefry4x64Core::from_seed(seed)),
+        }
+    }
+}
+
+impl RngCore for Threefry4x64Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        self.rng.fill_bytes(bytes)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(bytes)
+    }
+}
+
+impl From<Threefry4x64Core> for Threefry4x64Rng {
+    fn from(core: Threefry4x64Core) -> Self {
+        Threefry4x64Rng {
+            rng: BlockRng64::new(core),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_threefry4x64_kat() {
+        // Known-answer tests from `kat_vectors` in Random123 1.09.
+        assert_eq!(threefry4x64([0; 4], [0; 4]), [
+            0x09218ebde6c85537,
+            0x55941f5266d86105,
+            0x4bd25e16282434dc,
+            0xee29ec846bd2e40b,
+        ]);
+        assert_eq!(threefry4x64([!0; 4], [!0; 4]), [
+            0x29c24097942bba1b,
+            0x0371bbfb0f6f4e11,
+            0x3c231ffa33f83a1c,
+            0xcd29113fde32d168,
+        ]);
+        // This vector is for the reduced-round Threefry4x64-13:
+        let ctr = [
+            0x243f6a8885a308d3,
+            0x13198a2e03707344,
+            0xa4093822299f31d0,
+            0x082efa98ec4e6c89,
+        ];
+        let key = [
+            0x452821e638d01377,
+            0xbe5466cf34e90c6c,
+            0xc0ac29b7c97c50dd,
+            0x3f84d5b5b5470917,
+        ];
+        assert_eq!(threefry4x64_rounds(ctr, key, 13), [
+            0x4361288ef9c1900c,
+            0x8717291521782833,
+            0x0d19db18c20cf47e,
+            0xa0b41d63ac8581e5,
+        ]);
+        // and this one for Threefry4x64-20, with the same counter and key:
+        assert_eq!(threefry4x64(ctr, key), [
+            0xbb893fd42eac50eb,
+            0x7ca8b22905f3443a,
+            0xe204b8dcb4daace7,
+            0x3e1070a2327bfc09,
+        ]);
+    }
+
+    #[test]
+    fn test_threefry4x64_rng_true_values() {
+        // The first block is the all-zero known answer, the next uses counter 1.
+        let mut rng = Threefry4x64Rng::from_seed([0; 32]);
+        let mut results = [0u64; 8];
+        for x in results.iter_mut() {
+            *x = rng.next_u64();
+        }
+        assert_eq!(results[..4], [
+            0x09218ebde6c85537,
+            0x55941f5266d86105,
+            0x4bd25e16282434dc,
+            0xee29ec846bd2e40b,
+        ]);
+        assert_eq!(results[4..], threefry4x64([1, 0, 0, 0], [0; 4]));
+
+        // The stream selects the second counter word:
+        let mut rng = Threefry4x64Rng::from_seed([0xff; 32]);
+        rng.set_stream(7);
+        rng.set_word_pos(5 << 2);
+        assert_eq!(rng.next_u64(), threefry4x64([5, 7, 0, 0], [!0; 4])[0]);
+    }
+
+    #[test]
+    fn test_threefry4x64_word_pos() {
+        let mut rng1 = Threefry4x64Rng::seed_from_u64(42);
+        assert_eq!(rng1.get_word_pos(), 0);
+        let mut rng2 = rng1.clone();
+        for _ in 0..7 {
+            rng1.next_u64();
+        }
+        assert_eq!(rng1.get_word_pos(), 7);
+
+        rng2.set_word_pos(7);
+        assert_eq!(rng2.get_word_pos(), 7);
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+
+        // The position wraps at 2^66 words:
+        rng2.set_word_pos((1 << 66) - 1);
+        assert_eq!(rng2.get_word_pos(), (1 << 66) - 1);
+        rng2.next_u64();
+        assert_eq!(rng2.get_word_pos(), 0);
+    }
+
+    #[test]
+    fn test_threefry4x64_streams() {
+        let mut rng1 = Threefry4x64Rng::seed_from_u64(0);
+        let mut rng2 = rng1.clone();
+        rng1.next_u64();
+        rng2.next_u64();
+        rng2.set_stream(1);
+        assert_eq!(rng2.get_stream(), 1);
+        assert_eq!(rng2.get_word_pos(), 1);
+        assert_ne!(rng1.next_u64(), rng2.next_u64());
+
+        let mut rng3 = Threefry4x64Rng::seed_from_u64(0);
+        rng3.set_stream(1);
+        rng3.set_word_pos(2);
+        assert_eq!(rng2.next_u64(), rng3.next_u64());
+    }
+}
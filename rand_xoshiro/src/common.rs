@@ -0,0 +1,30 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Code shared by the xoshiro generators.
+
+/// Apply the jump polynomial `$jump` to `$self`, where `$self.s` is the
+/// state array and `$self.step()` advances the state by one step.
+macro_rules! impl_jump {
+    ($self:expr, $jump:expr) => {{
+        let jump: &[u64] = &$jump;
+        let mut s = [0u64; 4];
+        let n = $self.s.len();
+        for &j in jump {
+            for b in 0..64 {
+                if (j & 1 << b) != 0 {
+                    for (acc, x) in s[..n].iter_mut().zip($self.s.iter()) {
+                        *acc ^= *x;
+                    }
+                }
+                $self.step();
+            }
+        }
+        $self.s.copy_from_slice(&s[..n]);
+    }};
+}
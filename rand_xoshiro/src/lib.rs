@@ -0,0 +1,62 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The xoshiro random number generators.
+//!
+//! This is a native Rust implementation of a small selection of the
+//! [xoshiro/xoroshiro generators](http://xoshiro.di.unimi.it) by David
+//! Blackman and Sebastiano Vigna. As with `rand_pcg`, it is explicitly not a
+//! goal to re-implement every member of the family.
+//!
+//! This crate provides:
+//!
+//! -   `Xoshiro256PlusPlus`, a general purpose RNG with 256 bits of state
+//!     and 64-bit output. This is a good choice on 64-bit CPUs.
+//! -   `Xoshiro256StarStar`, the same generator with the `**` scrambler,
+//!     which has slightly different statistical properties.
+//! -   `Xoroshiro128PlusPlus`, a general purpose RNG with 128 bits of state
+//!     and 64-bit output, for when space is at a premium.
+//! -   `SplitMix64`, a simple RNG with 64 bits of state, recommended by the
+//!     authors for initialising the state of the other generators. This is
+//!     a re-export of `rand_core::SplitMix64`.
+//!
+//! The xoshiro generators provide `jump` and `long_jump` functions, which
+//! are equivalent to 2<sup>128</sup> and 2<sup>192</sup> calls to
+//! `next_u64` respectively (2<sup>64</sup> and 2<sup>96</sup> for
+//! `Xoroshiro128PlusPlus`). These can be used to generate non-overlapping
+//! sequences for parallel computations.
+//!
+//! As recommended by the authors, `seed_from_u64` initialises the state
+//! from the output of `SplitMix64`. An all-zero seed passed to `from_seed` is
+//! invalid for xoshiro generators; such a seed is replaced by
+//! `seed_from_u64(0)`.
+//!
+//! These generators are not suitable for cryptographic uses. They are
+//! considered value-stable (i.e. any change affecting the output given a
+//! fixed seed would be considered a breaking change to the crate).
+
+#![doc(
+    html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+    html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+    html_root_url = "https://rust-random.github.io/rand/"
+)]
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+#![allow(clippy::unreadable_literal)]
+#![no_std]
+
+#[macro_use]
+mod common;
+mod xoroshiro128plusplus;
+mod xoshiro256plusplus;
+mod xoshiro256starstar;
+
+pub use rand_core::SplitMix64;
+pub use self::xoroshiro128plusplus::Xoroshiro128PlusPlus;
+pub use self::xoshiro256plusplus::Xoshiro256PlusPlus;
+pub use self::xoshiro256starstar::Xoshiro256StarStar;
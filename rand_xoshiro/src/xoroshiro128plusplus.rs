@@ -0,0 +1,217 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+use rand_core::{impls, le, Error, RngCore, SeedableRng, SplitMix64};
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
+
+/// A xoroshiro128++ random number generator.
+///
+/// The xoroshiro128++ algorithm is not suitable for cryptographic
+/// purposes, but is very fast and has excellent statistical properties.
+///
+/// The algorithm used here is translated from [the `xoroshiro128plusplus.c` reference
+/// source code](http://xoshiro.di.unimi.it/xoroshiro128plusplus.c) by David Blackman and
+/// Sebastiano Vigna.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Xoroshiro128PlusPlus {
+    s: [u64; 2],
+}
+
+impl Xoroshiro128PlusPlus {
+    /// Jump forward, equivalently to 2^64 calls to `next_u64()`.
+    ///
+    /// This can be used to generate 2^64 non-overlapping subsequences for
+    /// parallel computations.
+    ///
+    /// ```
+    /// use rand_core::SeedableRng;
+    /// use rand_xoshiro::Xoroshiro128PlusPlus;
+    ///
+    /// let rng1 = Xoroshiro128PlusPlus::seed_from_u64(0);
+    /// let mut rng2 = rng1.clone();
+    /// rng2.jump();
+    /// let mut rng3 = rng2.clone();
+    /// rng3.jump();
+    /// ```
+    pub fn jump(&mut self) {
+        impl_jump!(self, [
+            0x2bd7a6a6e99c2ddc, 0x0992ccaf6a6fca05,
+        ]);
+    }
+
+    /// Jump forward, equivalently to 2^96 calls to `next_u64()`.
+    ///
+    /// This can be used to generate 2^32 starting points, from each of which
+    /// `jump()` will generate 2^32 non-overlapping subsequences for parallel
+    /// distributed computations.
+    pub fn long_jump(&mut self) {
+        impl_jump!(self, [
+            0x360fd5f2cf8d5d99, 0x9c6e6877736c46e3,
+        ]);
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let s0 = self.s[0];
+        let mut s1 = self.s[1];
+
+        s1 ^= s0;
+        self.s[0] = s0.rotate_left(49) ^ s1 ^ (s1 << 21);
+        self.s[1] = s1.rotate_left(28);
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Xoroshiro128PlusPlus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xoroshiro128PlusPlus {{}}")
+    }
+}
+
+impl SeedableRng for Xoroshiro128PlusPlus {
+    type Seed = [u8; 16];
+
+    /// Create a new `Xoroshiro128PlusPlus`. If `seed` is entirely 0, it will be
+    /// mapped to a different seed.
+    #[inline]
+    fn from_seed(seed: [u8; 16]) -> Xoroshiro128PlusPlus {
+        if seed.iter().all(|&x| x == 0) {
+            return Self::seed_from_u64(0);
+        }
+        let mut state = [0; 2];
+        le::read_u64_into(&seed, &mut state);
+        Xoroshiro128PlusPlus { s: state }
+    }
+
+    /// Seed a `Xoroshiro128PlusPlus` from a `u64` using `SplitMix64`.
+    fn seed_from_u64(seed: u64) -> Xoroshiro128PlusPlus {
+        let mut rng = SplitMix64::seed_from_u64(seed);
+        let mut state = [0; 2];
+        for x in state.iter_mut() {
+            *x = rng.next_u64();
+        }
+        Xoroshiro128PlusPlus { s: state }
+    }
+}
+
+impl RngCore for Xoroshiro128PlusPlus {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // The lowest bits have some linear dependencies, so we use the
+        // upper bits instead.
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[0]
+            .wrapping_add(self.s[1])
+            .rotate_left(17)
+            .wrapping_add(self.s[0]);
+        self.step();
+        result
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const SEED: [u8; 16] = [1, 0, 0, 0, 0, 0, 0, 0,
+                            2, 0, 0, 0, 0, 0, 0, 0];
+
+    #[test]
+    fn reference() {
+        let mut rng = Xoroshiro128PlusPlus::from_seed(SEED);
+        // These values were produced with the reference implementation:
+        // http://xoshiro.di.unimi.it/xoroshiro128plusplus.c
+        let expected: [u64; 10] = [
+            393217,
+            669327710093319,
+            1732421326133921491,
+            11394790081659126983,
+            9555452776773192676,
+            3586421180005889563,
+            1691397964866707553,
+            10735626796753111697,
+            15216282715349408991,
+            14247243556711267923,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn jump() {
+        let mut rng = Xoroshiro128PlusPlus::from_seed(SEED);
+        rng.jump();
+        // These values were produced by calling `jump` in the reference
+        // implementation.
+        let expected: [u64; 4] = [
+            6995778298204176446,
+            17606341508358386873,
+            18268233585225622342,
+            1634122034616564957,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn long_jump() {
+        let mut rng = Xoroshiro128PlusPlus::from_seed(SEED);
+        rng.long_jump();
+        // These values were produced by calling `long_jump` in the reference
+        // implementation.
+        let expected: [u64; 4] = [
+            13476878559037916028,
+            4599739792799904096,
+            9592342027630475676,
+            16396948912373680941,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn zero_seed() {
+        let mut rng1 = Xoroshiro128PlusPlus::from_seed([0; 16]);
+        let mut rng2 = Xoroshiro128PlusPlus::seed_from_u64(0);
+        assert_eq!(rng1.next_u64(), 8027914721839836897);
+        assert_eq!(rng2.next_u64(), 8027914721839836897);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn serde() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(0);
+        let buf = bincode::serialize(&rng).expect("Could not serialize");
+        let mut deserialized: Xoroshiro128PlusPlus =
+            bincode::deserialize(&buf).expect("Could not deserialize");
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), deserialized.next_u64());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+use rand_core::{impls, le, Error, RngCore, SeedableRng, SplitMix64};
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
+
+/// A xoshiro256** random number generator.
+///
+/// The xoshiro256** algorithm is not suitable for cryptographic
+/// purposes, but is very fast and has excellent statistical properties.
+///
+/// The algorithm used here is translated from [the `xoshiro256starstar.c` reference
+/// source code](http://xoshiro.di.unimi.it/xoshiro256starstar.c) by David Blackman and
+/// Sebastiano Vigna.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Xoshiro256StarStar {
+    s: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    /// Jump forward, equivalently to 2^128 calls to `next_u64()`.
+    ///
+    /// This can be used to generate 2^128 non-overlapping subsequences for
+    /// parallel computations.
+    ///
+    /// ```
+    /// use rand_core::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256StarStar;
+    ///
+    /// let rng1 = Xoshiro256StarStar::seed_from_u64(0);
+    /// let mut rng2 = rng1.clone();
+    /// rng2.jump();
+    /// let mut rng3 = rng2.clone();
+    /// rng3.jump();
+    /// ```
+    pub fn jump(&mut self) {
+        impl_jump!(self, [
+            0x180ec6d33cfd0aba, 0xd5a61266f0c9392c,
+            0xa9582618e03fc9aa, 0x39abdc4529b1661c,
+        ]);
+    }
+
+    /// Jump forward, equivalently to 2^192 calls to `next_u64()`.
+    ///
+    /// This can be used to generate 2^64 starting points, from each of which
+    /// `jump()` will generate 2^64 non-overlapping subsequences for parallel
+    /// distributed computations.
+    pub fn long_jump(&mut self) {
+        impl_jump!(self, [
+            0x76e15d3efefdcbbf, 0xc5004e441c522fb3,
+            0x77710069854ee241, 0x39109bb02acbe635,
+        ]);
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+
+        self.s[2] ^= t;
+
+        self.s[3] = self.s[3].rotate_left(45);
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Xoshiro256StarStar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xoshiro256StarStar {{}}")
+    }
+}
+
+impl SeedableRng for Xoshiro256StarStar {
+    type Seed = [u8; 32];
+
+    /// Create a new `Xoshiro256StarStar`. If `seed` is entirely 0, it will be
+    /// mapped to a different seed.
+    #[inline]
+    fn from_seed(seed: [u8; 32]) -> Xoshiro256StarStar {
+        if seed.iter().all(|&x| x == 0) {
+            return Self::seed_from_u64(0);
+        }
+        let mut state = [0; 4];
+        le::read_u64_into(&seed, &mut state);
+        Xoshiro256StarStar { s: state }
+    }
+
+    /// Seed a `Xoshiro256StarStar` from a `u64` using `SplitMix64`.
+    fn seed_from_u64(seed: u64) -> Xoshiro256StarStar {
+        let mut rng = SplitMix64::seed_from_u64(seed);
+        let mut state = [0; 4];
+        for x in state.iter_mut() {
+            *x = rng.next_u64();
+        }
+        Xoshiro256StarStar { s: state }
+    }
+}
+
+impl RngCore for Xoshiro256StarStar {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // The lowest bits have some linear dependencies, so we use the
+        // upper bits instead.
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        self.step();
+        result
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const SEED: [u8; 32] = [1, 0, 0, 0, 0, 0, 0, 0,
+                            2, 0, 0, 0, 0, 0, 0, 0,
+                            3, 0, 0, 0, 0, 0, 0, 0,
+                            4, 0, 0, 0, 0, 0, 0, 0];
+
+    #[test]
+    fn reference() {
+        let mut rng = Xoshiro256StarStar::from_seed(SEED);
+        // These values were produced with the reference implementation:
+        // http://xoshiro.di.unimi.it/xoshiro256starstar.c
+        let expected: [u64; 10] = [
+            11520,
+            0,
+            1509978240,
+            1215971899390074240,
+            1216172134540287360,
+            607988272756665600,
+            16172922978634559625,
+            8476171486693032832,
+            10595114339597558777,
+            2904607092377533576,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn jump() {
+        let mut rng = Xoshiro256StarStar::from_seed(SEED);
+        rng.jump();
+        // These values were produced by calling `jump` in the reference
+        // implementation.
+        let expected: [u64; 4] = [
+            13534147089533256664,
+            7126240192422241655,
+            3805973808039778091,
+            11547880530658420384,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn long_jump() {
+        let mut rng = Xoshiro256StarStar::from_seed(SEED);
+        rng.long_jump();
+        // These values were produced by calling `long_jump` in the reference
+        // implementation.
+        let expected: [u64; 4] = [
+            5942309088398569549,
+            15625447729937358436,
+            6925613901769781251,
+            16198770605655666946,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn zero_seed() {
+        let mut rng1 = Xoshiro256StarStar::from_seed([0; 32]);
+        let mut rng2 = Xoshiro256StarStar::seed_from_u64(0);
+        assert_eq!(rng1.next_u64(), 11091344671253066420);
+        assert_eq!(rng2.next_u64(), 11091344671253066420);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn serde() {
+        let mut rng = Xoshiro256StarStar::seed_from_u64(0);
+        let buf = bincode::serialize(&rng).expect("Could not serialize");
+        let mut deserialized: Xoshiro256StarStar =
+            bincode::deserialize(&buf).expect("Could not deserialize");
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), deserialized.next_u64());
+        }
+    }
+}
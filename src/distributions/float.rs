@@ -67,6 +67,49 @@ pub struct OpenClosed01;
 #[derive(Clone, Copy, Debug)]
 pub struct Open01;
 
+/// A distribution to sample floating point numbers uniformly in the half-open
+/// interval `[0, 1)`, where every representable value can be generated.
+///
+/// [`Standard`] only generates multiples of `ε/2`, so for example no value in
+/// `(0, 2^-53)` is ever sampled as an `f64`. This distribution instead
+/// samples a real number uniformly from `[0, 1)` and rounds it down to a
+/// float, as in Allen B. Downey's [Generating Pseudo-random Floating-Point
+/// Values](http://allendowney.com/research/rand/): the exponent is chosen
+/// with a geometric distribution and the fraction bits are uniform. Each
+/// value `x` is thus sampled with probability equal to the distance to the
+/// next larger float, so values in `[2^-k, 2^-k+1)` have total probability
+/// `2^-k`, down to the subnormal values.
+///
+/// Usually one `u32` (for `f32`) or one `u64` (for `f64`) is used; further
+/// values are only needed with probability `2^-9` and `2^-12` respectively.
+/// This is only implemented for `f32` and `f64`.
+///
+/// See also: [`Standard`] which samples from `[0, 1)` with fixed precision
+/// and [`UniformFullPrecision`] which samples from arbitrary ranges.
+///
+/// # Example
+/// ```
+/// use rand::{thread_rng, Rng};
+/// use rand::distributions::FullPrecision01;
+///
+/// let val: f64 = thread_rng().sample(FullPrecision01);
+/// println!("f64 from [0, 1): {:e}", val);
+/// ```
+///
+/// [`Standard`]: crate::distributions::Standard
+/// [`UniformFullPrecision`]: crate::distributions::uniform::UniformFullPrecision
+#[derive(Clone, Copy, Debug)]
+pub struct FullPrecision01;
+
+/// Sampling of every representable float in `[0, 1)`, as by
+/// [`FullPrecision01`].
+pub(crate) trait FullPrecisionFloat: Sized {
+    /// Sample a real number uniformly from `[0, 2^k)` and return it rounded
+    /// down to a float, where `2^(k-1)` is the smallest float with the
+    /// exponent bits `top`; `top = 0` samples the subnormal values.
+    fn sample_full_precision<R: Rng + ?Sized>(rng: &mut R, top: u32) -> Self;
+}
+
 
 // This trait is needed by both this lib and rand_distr hence is a hidden export
 #[doc(hidden)]
@@ -148,6 +191,52 @@ macro_rules! float_impls {
 float_impls! { f32, u32, f32, u32, 23, 127 }
 float_impls! { f64, u64, f64, u64, 52, 1023 }
 
+macro_rules! full_precision_impls {
+    ($ty:ident, $uty:ident, $fraction_bits:expr, $exponent_bias:expr) => {
+        impl FullPrecisionFloat for $ty {
+            #[inline]
+            fn sample_full_precision<R: Rng + ?Sized>(rng: &mut R, top: u32) -> $ty {
+                let float_size = mem::size_of::<$ty>() as u32 * 8;
+                let exponent_size = float_size - $fraction_bits;
+
+                // The low bits are the fraction; the number of leading zeros
+                // of the remaining high bits, continued in further values if
+                // they are all zero, is the distance of the exponent from
+                // `top`. Reaching exponent 0 gives a subnormal value (or
+                // zero), which has the same spacing as exponent 1.
+                let value: $uty = rng.gen();
+                let fraction = value & ((1 << $fraction_bits) - 1);
+                let mut exponent = top as $uty;
+                let zeros = value.leading_zeros();
+                if zeros < exponent_size {
+                    exponent = exponent.saturating_sub(zeros as $uty);
+                } else {
+                    exponent = exponent.saturating_sub(exponent_size as $uty);
+                    while exponent > 0 {
+                        let zeros = rng.gen::<$uty>().leading_zeros();
+                        exponent = exponent.saturating_sub(zeros as $uty);
+                        if zeros < float_size {
+                            break;
+                        }
+                    }
+                }
+                $ty::from_bits(exponent << $fraction_bits | fraction)
+            }
+        }
+
+        impl Distribution<$ty> for FullPrecision01 {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // The exponent of [0.5, 1)
+                $ty::sample_full_precision(rng, $exponent_bias - 1)
+            }
+        }
+    };
+}
+
+full_precision_impls! { f32, u32, 23, 127 }
+full_precision_impls! { f64, u64, 52, 1023 }
+
 #[cfg(feature = "simd_support")]
 float_impls! { f32x2, u32x2, f32, u32, 23, 127 }
 #[cfg(feature = "simd_support")]
@@ -251,6 +340,119 @@ mod tests {
     #[cfg(feature = "simd_support")]
     test_f64! { f64x8_edge_cases, f64x8, f64x8::splat(0.0), f64x8::splat(EPSILON64) }
 
+    #[test]
+    fn full_precision_edge_cases() {
+        let mut zeros = StepRng::new(0, 0);
+        assert_eq!(zeros.sample::<f32, _>(FullPrecision01), 0.0);
+        assert_eq!(zeros.sample::<f64, _>(FullPrecision01), 0.0);
+
+        let mut max = StepRng::new(!0, 0);
+        assert_eq!(max.sample::<f32, _>(FullPrecision01), 1.0 - EPSILON32 / 2.0);
+        assert_eq!(max.sample::<f64, _>(FullPrecision01), 1.0 - EPSILON64 / 2.0);
+
+        // The largest and the subnormal values
+        assert_eq!(f32::sample_full_precision(&mut max, 254), ::core::f32::MAX);
+        assert_eq!(f64::sample_full_precision(&mut max, 2046), ::core::f64::MAX);
+        assert_eq!(f32::sample_full_precision(&mut max, 0), f32::from_bits((1 << 23) - 1));
+        let mut three = StepRng::new(3, 0);
+        assert_eq!(f64::sample_full_precision(&mut three, 20), f64::from_bits(3));
+
+        // Fraction 5, all exponent bits zero, then a zero value and a value
+        // without leading zeros.
+        let mut deep = StepRng::new(5, 5u64.wrapping_neg());
+        let x: f32 = deep.sample(FullPrecision01);
+        assert_eq!(x, f32::from_bits((127 - 42) << 23 | 5));
+        let mut deep = StepRng::new(5, 5u64.wrapping_neg());
+        let x: f64 = deep.sample(FullPrecision01);
+        assert_eq!(x, f64::from_bits((1023 - 77) << 52 | 5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn full_precision_tail() {
+        use crate::RngCore;
+
+        // Forces the multi-word exponent path of `sample_full_precision`: the
+        // exponent bits of the first value are cleared and the second value is
+        // zero, followed by the output of `rng`.
+        struct DeepRng<R> {
+            rng: R,
+            fraction_mask: u64,
+            index: usize,
+        }
+
+        impl<R: RngCore> DeepRng<R> {
+            fn filter(&mut self, x: u64) -> u64 {
+                self.index += 1;
+                match self.index {
+                    1 => x & self.fraction_mask,
+                    2 => 0,
+                    _ => x,
+                }
+            }
+        }
+
+        impl<R: RngCore> RngCore for DeepRng<R> {
+            fn next_u32(&mut self) -> u32 {
+                let x = self.rng.next_u32();
+                self.filter(u64::from(x)) as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let x = self.rng.next_u64();
+                self.filter(x)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), crate::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        // Far below the precision of `Standard` (below 2^-41 for `f32` and
+        // 2^-76 for `f64`), the exponent is still geometric, and the fraction
+        // bits are all random.
+        const N: u32 = 10_000;
+        macro_rules! t {
+            ($ty:ident, $fraction_bits:expr, $exponent_bias:expr, $seed:expr) => {{
+                let float_size = mem::size_of::<$ty>() as i64 * 8;
+                let exponent_size = float_size - $fraction_bits;
+                // The exponent of [0.5, 1), less the zeros forced by `DeepRng`
+                let top = $exponent_bias - 1 - exponent_size - float_size;
+                let mut rng = crate::test::rng($seed);
+                let mut zeros = [0u32; 8];
+                let mut odd = 0;
+                for _ in 0..N {
+                    let mut deep = DeepRng {
+                        rng: &mut rng,
+                        fraction_mask: (1 << $fraction_bits) - 1,
+                        index: 0,
+                    };
+                    let x: $ty = deep.sample(FullPrecision01);
+                    let exponent = (x.to_bits() >> $fraction_bits) as i64;
+                    assert!(0 < exponent && exponent <= top, "{:e}", x);
+                    if let Some(count) = zeros.get_mut((top - exponent) as usize) {
+                        *count += 1;
+                    }
+                    odd += (x.to_bits() & 1) as u32;
+                }
+                for (k, &count) in zeros.iter().enumerate() {
+                    let p = (0.5f64).powi(k as i32 + 1);
+                    let what = format!("{}: exponent {}", stringify!($ty), top - k as i64);
+                    crate::test::assert_binomial(count, N, p, &what);
+                }
+                crate::test::assert_binomial(odd, N, 0.5, "odd fraction");
+            }};
+        }
+        t!(f32, 23, 127, 0x5f32);
+        t!(f64, 52, 1023, 0x5f64);
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: Copy + core::fmt::Debug + PartialEq, D: Distribution<T>>(
@@ -285,6 +487,13 @@ mod tests {
             0.8166436635290656,
         ]);
 
+        test_samples(&FullPrecision01, 0f32, &[0.0035963906, 0.5589302, 0.06651684]);
+        test_samples(&FullPrecision01, 0f64, &[
+            0.9714417474729493,
+            0.17856301042816355,
+            0.9862229075261894,
+        ]);
+
         #[cfg(feature = "simd_support")]
         {
             // We only test a sub-set of types here. Values are identical to
@@ -71,7 +71,8 @@
 //! range between 0 and 1 is standard, but the exact bounds (open vs closed)
 //! and accuracy differ. In addition to the [`Standard`] distribution Rand offers
 //! [`Open01`] and [`OpenClosed01`]. See "Floating point implementation" section of
-//! [`Standard`] documentation for more details.
+//! [`Standard`] documentation for more details. [`FullPrecision01`] can
+//! generate every float in `[0, 1)`, including those close to zero.
 //!
 //! # Non-uniform sampling
 //!
@@ -97,7 +98,7 @@ use crate::Rng;
 use core::iter;
 
 pub use self::bernoulli::{Bernoulli, BernoulliError};
-pub use self::float::{Open01, OpenClosed01};
+pub use self::float::{FullPrecision01, Open01, OpenClosed01};
 pub use self::other::Alphanumeric;
 #[doc(inline)] pub use self::uniform::Uniform;
 
@@ -15,14 +15,15 @@
 //! [`Uniform`].
 //!
 //! This distribution is provided with support for several primitive types
-//! (all integer and floating-point types) as well as [`std::time::Duration`],
-//! and supports extension to user-defined types via a type-specific *back-end*
-//! implementation.
+//! (all integer and floating-point types and `char`) as well as
+//! [`std::time::Duration`], and supports extension to user-defined types via a
+//! type-specific *back-end* implementation.
 //!
-//! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
-//! back-ends supporting sampling from primitive integer and floating-point
-//! ranges as well as from [`std::time::Duration`]; these types do not normally
-//! need to be used directly (unless implementing a derived back-end).
+//! The types [`UniformInt`], [`UniformFloat`], [`UniformChar`] and
+//! [`UniformDuration`] are the back-ends supporting sampling from primitive
+//! integer, floating-point and `char` ranges as well as from
+//! [`std::time::Duration`]; these types do not normally need to be used
+//! directly (unless implementing a derived back-end).
 //!
 //! # Example usage
 //!
@@ -46,12 +47,18 @@
 //! To extend [`Uniform`] to support your own types, write a back-end which
 //! implements the [`UniformSampler`] trait, then implement the [`SampleUniform`]
 //! helper trait to "register" your back-end. See the `MyF32` example below.
+//! For a newtype wrapping a supported type, such as an integer, the generic
+//! back-end [`UniformNewtype`] can be used instead.
 //!
 //! At a minimum, the back-end needs to store any parameters needed for sampling
 //! (e.g. the target range) and implement `new`, `new_inclusive` and `sample`.
 //! Those methods should include an assert to check the range is valid (i.e.
 //! `low < high`). The example below merely wraps another back-end.
 //!
+//! The back-end should also implement `try_new` and `try_new_inclusive`,
+//! returning a [`UniformError`] instead of panicking on an invalid range; the
+//! default implementations call `new` and `new_inclusive` and so still panic.
+//!
 //! The `new`, `new_inclusive` and `sample_single` functions use arguments of
 //! type SampleBorrow<X> in order to support passing in values by reference or
 //! by value. In the implementation of these functions, you can choose to
@@ -97,19 +104,24 @@
 //! ```
 //!
 //! [`SampleUniform`]: crate::distributions::uniform::SampleUniform
+//! [`UniformError`]: crate::distributions::uniform::UniformError
 //! [`UniformSampler`]: crate::distributions::uniform::UniformSampler
 //! [`UniformInt`]: crate::distributions::uniform::UniformInt
 //! [`UniformFloat`]: crate::distributions::uniform::UniformFloat
+//! [`UniformChar`]: crate::distributions::uniform::UniformChar
+//! [`UniformNewtype`]: crate::distributions::uniform::UniformNewtype
 //! [`UniformDuration`]: crate::distributions::uniform::UniformDuration
 //! [`SampleBorrow::borrow`]: crate::distributions::uniform::SampleBorrow::borrow
 
 #[cfg(not(feature = "std"))] use core::time::Duration;
 #[cfg(feature = "std")] use std::time::Duration;
 
-use crate::distributions::float::IntoFloat;
-use crate::distributions::utils::{BoolAsSIMD, FloatAsSIMD, FloatSIMDUtils, WideningMultiply};
+use crate::distributions::float::{FullPrecisionFloat, IntoFloat};
+use crate::distributions::utils::{FloatAsSIMD, FloatSIMDUtils, WideningMultiply};
 use crate::distributions::Distribution;
 use crate::Rng;
+use core::fmt;
+use core::marker::PhantomData;
 
 #[cfg(not(feature = "std"))]
 #[allow(unused_imports)] // rustc doesn't detect that this is actually used
@@ -181,6 +193,28 @@ impl<X: SampleUniform> Uniform<X> {
     {
         Uniform(X::Sampler::new_inclusive(low, high))
     }
+
+    /// Create a new `Uniform` instance which samples uniformly from the half
+    /// open range `[low, high)` (excluding `high`), or return an error if
+    /// `low >= high` or, for floating-point types, a bound is not finite.
+    pub fn try_new<B1, B2>(low: B1, high: B2) -> Result<Uniform<X>, UniformError>
+    where
+        B1: SampleBorrow<X> + Sized,
+        B2: SampleBorrow<X> + Sized,
+    {
+        X::Sampler::try_new(low, high).map(Uniform)
+    }
+
+    /// Create a new `Uniform` instance which samples uniformly from the closed
+    /// range `[low, high]` (inclusive), or return an error if `low > high` or,
+    /// for floating-point types, a bound is not finite.
+    pub fn try_new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Uniform<X>, UniformError>
+    where
+        B1: SampleBorrow<X> + Sized,
+        B2: SampleBorrow<X> + Sized,
+    {
+        X::Sampler::try_new_inclusive(low, high).map(Uniform)
+    }
 }
 
 impl<X: SampleUniform> Distribution<X> for Uniform<X> {
@@ -189,6 +223,29 @@ impl<X: SampleUniform> Distribution<X> for Uniform<X> {
     }
 }
 
+/// Error type returned from [`Uniform::try_new`] and the other fallible
+/// constructors of this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UniformError {
+    /// `low > high`, or `low == high` for a half-open range.
+    EmptyRange,
+
+    /// A bound is infinite or NaN (floating-point types only).
+    NonFinite,
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for UniformError {}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UniformError::EmptyRange => write!(f, "low > high (or low == high if exclusive)"),
+            UniformError::NonFinite => write!(f, "non-finite boundaries"),
+        }
+    }
+}
+
 /// Helper trait for creating objects using the correct implementation of
 /// [`UniformSampler`] for the sampling type.
 ///
@@ -235,6 +292,38 @@ pub trait UniformSampler: Sized {
         B1: SampleBorrow<Self::X> + Sized,
         B2: SampleBorrow<Self::X> + Sized;
 
+    /// Construct self, with inclusive lower bound and exclusive upper bound
+    /// `[low, high)`, or return an error if the range is invalid.
+    ///
+    /// The default implementation calls [`new`], which panics on an invalid
+    /// range; implementations should override this to return an error
+    /// instead.
+    ///
+    /// [`new`]: UniformSampler::new
+    fn try_new<B1, B2>(low: B1, high: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Ok(UniformSampler::new(low, high))
+    }
+
+    /// Construct self, with inclusive bounds `[low, high]`, or return an error
+    /// if the range is invalid.
+    ///
+    /// The default implementation calls [`new_inclusive`], which panics on an
+    /// invalid range; implementations should override this to return an error
+    /// instead.
+    ///
+    /// [`new_inclusive`]: UniformSampler::new_inclusive
+    fn try_new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Ok(UniformSampler::new_inclusive(low, high))
+    }
+
     /// Sample a value.
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X;
 
@@ -265,6 +354,26 @@ pub trait UniformSampler: Sized {
         let uniform: Self = UniformSampler::new(low, high);
         uniform.sample(rng)
     }
+
+    /// Sample a single value uniformly from a range with inclusive lower bound
+    /// and exclusive upper bound `[low, high)`, or return an error if the
+    /// range is invalid.
+    ///
+    /// This is the fallible counterpart of [`sample_single`], used by
+    /// [`Rng::try_gen_range`]. By default this is implemented using
+    /// `UniformSampler::try_new(low, high)` followed by `sample(rng)`.
+    ///
+    /// [`sample_single`]: UniformSampler::sample_single
+    fn try_sample_single<R: Rng + ?Sized, B1, B2>(
+        low: B1, high: B2, rng: &mut R,
+    ) -> Result<Self::X, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let uniform: Self = UniformSampler::try_new(low, high)?;
+        Ok(uniform.sample(rng))
+    }
 }
 
 impl<X: SampleUniform> From<::core::ops::Range<X>> for Uniform<X> {
@@ -333,9 +442,13 @@ where Borrowed: SampleUniform
 ///
 /// The optimum `zone` is the largest product of `range` which fits in our
 /// (unsigned) target type. We calculate this by calculating how many numbers we
-/// must reject: `reject = (MAX + 1) % range = (MAX - range + 1) % range`. Any (large)
-/// product of `range` will suffice, thus in `sample_single` we multiply by a
-/// power of 2 via bit-shifting (faster but may cause more rejections).
+/// must reject: `reject = (MAX + 1) % range = (MAX - range + 1) % range`.
+///
+/// Computing `reject` needs a modulus, which is worthwhile when sampling
+/// repeatedly but dominates the cost of a single sample. `sample_single`
+/// therefore uses [Lemire's method](https://arxiv.org/abs/1805.10941): since
+/// `reject < range`, a sample whose low word is at least `range` is accepted
+/// without knowing `reject`, and the modulus is only computed otherwise.
 ///
 /// The smallest integer PRNGs generate is `u32`. For 8- and 16-bit outputs we
 /// use `u32` for our `zone` and samples (because it's not slower and because
@@ -374,25 +487,46 @@ macro_rules! uniform_int_impl {
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
-                let low = *low_b.borrow();
-                let high = *high_b.borrow();
-                assert!(low < high, "Uniform::new called with `low >= high`");
-                UniformSampler::new_inclusive(low, high - 1)
+                Self::try_new(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
             }
 
             #[inline] // if the range is constant, this helps LLVM to do the
                       // calculations at compile-time.
             fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_new_inclusive(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+            }
+
+            #[inline]
+            fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
             where
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
                 let low = *low_b.borrow();
                 let high = *high_b.borrow();
-                assert!(
-                    low <= high,
-                    "Uniform::new_inclusive called with `low > high`"
-                );
+                if low >= high {
+                    return Err(UniformError::EmptyRange);
+                }
+                Self::try_new_inclusive(low, high - 1)
+            }
+
+            #[inline]
+            fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = *low_b.borrow();
+                let high = *high_b.borrow();
+                if low > high {
+                    return Err(UniformError::EmptyRange);
+                }
                 let unsigned_max = ::core::$u_large::MAX;
 
                 let range = high.wrapping_sub(low).wrapping_add(1) as $unsigned;
@@ -403,12 +537,12 @@ macro_rules! uniform_int_impl {
                     0
                 };
 
-                UniformInt {
+                Ok(UniformInt {
                     low: low,
                     // These are really $unsigned values, but store as $ty:
                     range: range as $ty,
                     z: ints_to_reject as $unsigned as $ty,
-                }
+                })
             }
 
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
@@ -429,35 +563,43 @@ macro_rules! uniform_int_impl {
                 }
             }
 
+            #[inline]
             fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R) -> Self::X
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_sample_single(low_b, high_b, rng)
+                    .unwrap_or_else(|e| panic!("UniformSampler::sample_single: {}", e))
+            }
+
+            fn try_sample_single<R: Rng + ?Sized, B1, B2>(
+                low_b: B1, high_b: B2, rng: &mut R,
+            ) -> Result<Self::X, UniformError>
             where
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
                 let low = *low_b.borrow();
                 let high = *high_b.borrow();
-                assert!(low < high, "UniformSampler::sample_single: low >= high");
+                if low >= high {
+                    return Err(UniformError::EmptyRange);
+                }
                 let range = high.wrapping_sub(low) as $unsigned as $u_large;
-                let zone = if ::core::$unsigned::MAX <= ::core::u16::MAX as $unsigned {
-                    // Using a modulus is faster than the approximation for
-                    // i8 and i16. I suppose we trade the cost of one
-                    // modulus for near-perfect branch prediction.
-                    let unsigned_max: $u_large = ::core::$u_large::MAX;
-                    let ints_to_reject = (unsigned_max - range + 1) % range;
-                    unsigned_max - ints_to_reject
-                } else {
-                    // conservative but fast approximation. `- 1` is necessary to allow the
-                    // same comparison without bias.
-                    (range << range.leading_zeros()).wrapping_sub(1)
-                };
 
-                loop {
-                    let v: $u_large = rng.gen();
-                    let (hi, lo) = v.wmul(range);
-                    if lo <= zone {
-                        return low.wrapping_add(hi as $ty);
+                // Lemire's method: we must reject `lo < ints_to_reject`, and
+                // since `ints_to_reject < range` the modulus is only needed
+                // if `lo < range`, which is rare unless the range is large.
+                let (mut hi, mut lo) = rng.gen::<$u_large>().wmul(range);
+                if lo < range {
+                    let ints_to_reject = range.wrapping_neg() % range;
+                    while lo < ints_to_reject {
+                        let (h, l) = rng.gen::<$u_large>().wmul(range);
+                        hi = h;
+                        lo = l;
                     }
                 }
+                Ok(low.wrapping_add(hi as $ty))
             }
         }
     };
@@ -502,10 +644,8 @@ macro_rules! uniform_simd_int_impl {
                 where B1: SampleBorrow<Self::X> + Sized,
                       B2: SampleBorrow<Self::X> + Sized
             {
-                let low = *low_b.borrow();
-                let high = *high_b.borrow();
-                assert!(low.lt(high).all(), "Uniform::new called with `low >= high`");
-                UniformSampler::new_inclusive(low, high - 1)
+                Self::try_new(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
             }
 
             #[inline] // if the range is constant, this helps LLVM to do the
@@ -513,11 +653,34 @@ macro_rules! uniform_simd_int_impl {
             fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
                 where B1: SampleBorrow<Self::X> + Sized,
                       B2: SampleBorrow<Self::X> + Sized
+            {
+                Self::try_new_inclusive(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+            }
+
+            #[inline]
+            fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
             {
                 let low = *low_b.borrow();
                 let high = *high_b.borrow();
-                assert!(low.le(high).all(),
-                        "Uniform::new_inclusive called with `low > high`");
+                if !low.lt(high).all() {
+                    return Err(UniformError::EmptyRange);
+                }
+                Self::try_new_inclusive(low, high - 1)
+            }
+
+            #[inline]
+            fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+                where B1: SampleBorrow<Self::X> + Sized,
+                      B2: SampleBorrow<Self::X> + Sized
+            {
+                let low = *low_b.borrow();
+                let high = *high_b.borrow();
+                if !low.le(high).all() {
+                    return Err(UniformError::EmptyRange);
+                }
                 let unsigned_max = ::core::$u_scalar::MAX;
 
                 // NOTE: these may need to be replaced with explicitly
@@ -534,12 +697,12 @@ macro_rules! uniform_simd_int_impl {
                 // zero which means only one sample is needed.
                 let zone = unsigned_max - ints_to_reject;
 
-                UniformInt {
+                Ok(UniformInt {
                     low: low,
                     // These are really $unsigned values, but store as $ty:
                     range: range.cast(),
                     z: zone.cast(),
-                }
+                })
             }
 
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
@@ -624,6 +787,124 @@ uniform_simd_int_impl! {
 }
 
 
+/// The back-end implementing [`UniformSampler`] for `char`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// This samples uniformly from the Unicode scalar values in the range, i.e.
+/// skipping the surrogate code points `U+D800` to `U+DFFF`, which are not
+/// valid `char`s: the range is mapped to a range of `u32` without the gap,
+/// sampled with [`UniformInt`], and mapped back.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformChar {
+    sampler: UniformInt<u32>,
+}
+
+/// The first UTF-16 surrogate code point.
+const CHAR_SURROGATE_START: u32 = 0xD800;
+/// The number of UTF-16 surrogate code points.
+const CHAR_SURROGATE_LEN: u32 = 0xE000 - CHAR_SURROGATE_START;
+
+/// Map a `char` to a `u32`, closing the surrogate gap.
+#[inline]
+fn char_to_comp_u32(c: char) -> u32 {
+    match c as u32 {
+        c if c >= CHAR_SURROGATE_START => c - CHAR_SURROGATE_LEN,
+        c => c,
+    }
+}
+
+/// The inverse of `char_to_comp_u32`.
+#[inline]
+fn comp_u32_to_char(mut x: u32) -> char {
+    if x >= CHAR_SURROGATE_START {
+        x += CHAR_SURROGATE_LEN;
+    }
+    // SAFETY: x is not a surrogate nor greater than char::MAX, since it was
+    // mapped from a char by `char_to_comp_u32`, or sampled between two
+    // such values.
+    unsafe { ::core::char::from_u32_unchecked(x) }
+}
+
+impl SampleUniform for char {
+    type Sampler = UniformChar;
+}
+
+impl UniformSampler for UniformChar {
+    type X = char;
+
+    #[inline]
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self::try_new(low_b, high_b)
+            .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self::try_new_inclusive(low_b, high_b)
+            .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+    }
+
+    #[inline]
+    fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let low = char_to_comp_u32(*low_b.borrow());
+        let high = char_to_comp_u32(*high_b.borrow());
+        UniformInt::<u32>::try_new(low, high).map(|sampler| UniformChar { sampler })
+    }
+
+    #[inline]
+    fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let low = char_to_comp_u32(*low_b.borrow());
+        let high = char_to_comp_u32(*high_b.borrow());
+        UniformInt::<u32>::try_new_inclusive(low, high).map(|sampler| UniformChar { sampler })
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        comp_u32_to_char(self.sampler.sample(rng))
+    }
+
+    #[inline]
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R) -> Self::X
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self::try_sample_single(low_b, high_b, rng)
+            .unwrap_or_else(|e| panic!("UniformSampler::sample_single: {}", e))
+    }
+
+    #[inline]
+    fn try_sample_single<R: Rng + ?Sized, B1, B2>(
+        low_b: B1, high_b: B2, rng: &mut R,
+    ) -> Result<Self::X, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let low = char_to_comp_u32(*low_b.borrow());
+        let high = char_to_comp_u32(*high_b.borrow());
+        UniformInt::<u32>::try_sample_single(low, high, rng).map(comp_u32_to_char)
+    }
+}
+
+
 /// The back-end implementing [`UniformSampler`] for floating-point types.
 ///
 /// Unless you are implementing [`UniformSampler`] for your own type, this type
@@ -640,6 +921,13 @@ uniform_simd_int_impl! {
 /// multiply and addition. Values produced this way have what equals 23 bits of
 /// random digits for an `f32`, and 52 for an `f64`.
 ///
+/// Since rounding is monotonic, the result is largest for the largest value
+/// in `[0, 1)`. [`new`] and [`new_inclusive`] therefore start from the exact
+/// scale and decrease it to the largest value for which that largest result is
+/// below `high` (or at most `high` for a closed range), so `high` is never
+/// returned for a half-open range. If `high - low` overflows, half of the
+/// range is used, and the result doubled, which is exact.
+///
 /// [`new`]: UniformSampler::new
 /// [`new_inclusive`]: UniformSampler::new_inclusive
 /// [`Standard`]: crate::distributions::Standard
@@ -647,6 +935,9 @@ uniform_simd_int_impl! {
 pub struct UniformFloat<X> {
     low: X,
     scale: X,
+    // `Some` if `high - low` overflows in any lane: 2 in those lanes, in
+    // which `low` and `scale` are halved, otherwise 1
+    factor: Option<X>,
 }
 
 macro_rules! uniform_float_impl {
@@ -655,6 +946,63 @@ macro_rules! uniform_float_impl {
             type Sampler = UniformFloat<$ty>;
         }
 
+        impl UniformFloat<$ty> {
+            /// Halve `low` and `high` in the lanes where `high - low`
+            /// overflows, returning them with the factor to undo this, if any.
+            #[inline]
+            fn halve_overflowing(low: $ty, high: $ty) -> ($ty, $ty, Option<$ty>) {
+                if (high - low).all_finite() {
+                    return (low, high, None);
+                }
+                let overflow = !(high - low).finite_mask();
+                let factor = <$ty>::splat(2.0).select(overflow, <$ty>::splat(1.0));
+                (low / factor, high / factor, Some(factor))
+            }
+
+            /// Decrease `scale` in each lane to the largest value for which
+            /// the largest result, `max_rand * scale + low`, is below `high`
+            /// (or at most `high` if `inclusive`).
+            fn decrease_scale(low: $ty, high: $ty, mut scale: $ty, inclusive: bool) -> $ty {
+                let max_rand =
+                    (::core::$u_scalar::MAX >> $bits_to_discard).into_float_with_exponent(0) - 1.0;
+                for lane in 0..<$ty>::lanes() {
+                    let low: $f_scalar = low.extract(lane);
+                    let high: $f_scalar = high.extract(lane);
+                    let in_range = |scale: $f_scalar| {
+                        let max = max_rand * scale + low;
+                        max < high || (inclusive && max == high)
+                    };
+                    // `scale` is not negative, so its bits are ordered like
+                    // its values, and the largest result is monotonic in
+                    // both. Step down by growing amounts until in range, then
+                    // bisect. Eventually 0 is in range since `low <= high`.
+                    let mut bad: $u_scalar = scale.extract(lane).to_bits();
+                    if in_range(<$f_scalar>::from_bits(bad)) {
+                        continue;
+                    }
+                    let mut step = 1;
+                    let mut good = loop {
+                        let bits = bad.saturating_sub(step);
+                        if in_range(<$f_scalar>::from_bits(bits)) {
+                            break bits;
+                        }
+                        bad = bits;
+                        step *= 2;
+                    };
+                    while bad - good > 1 {
+                        let mid = good + (bad - good) / 2;
+                        if in_range(<$f_scalar>::from_bits(mid)) {
+                            good = mid;
+                        } else {
+                            bad = mid;
+                        }
+                    }
+                    scale = scale.replace(lane, <$f_scalar>::from_bits(good));
+                }
+                scale
+            }
+        }
+
         impl UniformSampler for UniformFloat<$ty> {
             type X = $ty;
 
@@ -663,64 +1011,63 @@ macro_rules! uniform_float_impl {
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
-                let low = *low_b.borrow();
-                let high = *high_b.borrow();
-                assert!(low.all_lt(high), "Uniform::new called with `low >= high`");
-                assert!(
-                    low.all_finite() && high.all_finite(),
-                    "Uniform::new called with non-finite boundaries"
-                );
-                let max_rand = <$ty>::splat(
-                    (::core::$u_scalar::MAX >> $bits_to_discard).into_float_with_exponent(0) - 1.0,
-                );
+                Self::try_new(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
+            }
 
-                let mut scale = high - low;
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_new_inclusive(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+            }
 
-                loop {
-                    let mask = (scale * max_rand + low).ge_mask(high);
-                    if mask.none() {
-                        break;
-                    }
-                    scale = scale.decrease_masked(mask);
+            fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = *low_b.borrow();
+                let high = *high_b.borrow();
+                if !(low.all_finite() && high.all_finite()) {
+                    return Err(UniformError::NonFinite);
                 }
+                if !low.all_lt(high) {
+                    return Err(UniformError::EmptyRange);
+                }
+                let (low, high, factor) = Self::halve_overflowing(low, high);
+                let scale = Self::decrease_scale(low, high, high - low, false);
 
                 debug_assert!(<$ty>::splat(0.0).all_le(scale));
 
-                UniformFloat { low, scale }
+                Ok(UniformFloat { low, scale, factor })
             }
 
-            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
             where
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
                 let low = *low_b.borrow();
                 let high = *high_b.borrow();
-                assert!(
-                    low.all_le(high),
-                    "Uniform::new_inclusive called with `low > high`"
-                );
-                assert!(
-                    low.all_finite() && high.all_finite(),
-                    "Uniform::new_inclusive called with non-finite boundaries"
-                );
+                if !(low.all_finite() && high.all_finite()) {
+                    return Err(UniformError::NonFinite);
+                }
+                if !low.all_le(high) {
+                    return Err(UniformError::EmptyRange);
+                }
                 let max_rand = <$ty>::splat(
                     (::core::$u_scalar::MAX >> $bits_to_discard).into_float_with_exponent(0) - 1.0,
                 );
 
-                let mut scale = (high - low) / max_rand;
-
-                loop {
-                    let mask = (scale * max_rand + low).gt_mask(high);
-                    if mask.none() {
-                        break;
-                    }
-                    scale = scale.decrease_masked(mask);
-                }
+                let (low, high, factor) = Self::halve_overflowing(low, high);
+                let scale = Self::decrease_scale(low, high, (high - low) / max_rand, true);
 
                 debug_assert!(<$ty>::splat(0.0).all_le(scale));
 
-                UniformFloat { low, scale }
+                Ok(UniformFloat { low, scale, factor })
             }
 
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
@@ -736,22 +1083,44 @@ macro_rules! uniform_float_impl {
                 // faster for others). However, the order of multiplication and
                 // addition is important, because on some platforms (e.g. ARM)
                 // it will be optimized to a single (non-FMA) instruction.
-                value0_1 * self.scale + self.low
+                let value = value0_1 * self.scale + self.low;
+
+                // Only for ranges too wide for `high - low`; multiplying by
+                // the factor is exact.
+                match self.factor {
+                    None => value,
+                    Some(factor) => value * factor,
+                }
             }
 
             #[inline]
             fn sample_single<R: Rng + ?Sized, B1, B2>(low_b: B1, high_b: B2, rng: &mut R) -> Self::X
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_sample_single(low_b, high_b, rng)
+                    .unwrap_or_else(|e| panic!("UniformSampler::sample_single: {}", e))
+            }
+
+            #[inline]
+            fn try_sample_single<R: Rng + ?Sized, B1, B2>(
+                low_b: B1, high_b: B2, rng: &mut R,
+            ) -> Result<Self::X, UniformError>
             where
                 B1: SampleBorrow<Self::X> + Sized,
                 B2: SampleBorrow<Self::X> + Sized,
             {
                 let low = *low_b.borrow();
                 let high = *high_b.borrow();
-                assert!(
-                    low.all_lt(high),
-                    "UniformSampler::sample_single: low >= high"
-                );
-                let mut scale = high - low;
+                if !low.all_lt(high) {
+                    return Err(if low.all_finite() && high.all_finite() {
+                        UniformError::EmptyRange
+                    } else {
+                        UniformError::NonFinite
+                    });
+                }
+                let scale = high - low;
 
                 loop {
                     // Generate a value in the range [1, 2)
@@ -768,7 +1137,7 @@ macro_rules! uniform_float_impl {
 
                     debug_assert!(low.all_le(res) || !scale.all_finite());
                     if res.all_lt(high) {
-                        return res;
+                        return Ok(res);
                     }
 
                     // This handles a number of edge cases.
@@ -797,14 +1166,13 @@ macro_rules! uniform_float_impl {
                     // appropriate checks.
                     //
                     // Likewise `high - low` overflowing to infinity is also
-                    // rare, so handle it here after the common case.
-                    let mask = !scale.finite_mask();
-                    if mask.any() {
-                        assert!(
-                            low.all_finite() && high.all_finite(),
-                            "Uniform::sample_single: low and high must be finite"
-                        );
-                        scale = scale.decrease_masked(mask);
+                    // rare, so handle it here after the common case, with the
+                    // constructor which samples from half of the range.
+                    if !scale.all_finite() {
+                        if !(low.all_finite() && high.all_finite()) {
+                            return Err(UniformError::NonFinite);
+                        }
+                        return Self::try_new(low, high).map(|uniform| uniform.sample(rng));
                     }
                 }
             }
@@ -832,6 +1200,205 @@ uniform_float_impl! { f64x4, u64x4, f64, u64, 64 - 52 }
 uniform_float_impl! { f64x8, u64x8, f64, u64, 64 - 52 }
 
 
+/// A back-end implementing [`UniformSampler`] for `f32` and `f64` where
+/// every representable value in the range can be generated.
+///
+/// [`UniformFloat`] computes `low + scale * x` with `x` of fixed precision,
+/// so for example a value in `(0, 2^-52)` is never sampled from the range
+/// `[-1, 1)`. This back-end instead samples a real number uniformly from the
+/// range and rounds it down to a float, like [`FullPrecision01`]: each value
+/// `x` is sampled with probability proportional to the distance to the next
+/// larger float. A closed range is sampled as if it extended to the float
+/// after `high`, so that `high` is sampled like any other value.
+///
+/// The values are generated from their bits: within a range of exponents
+/// with equal spacing the bits are uniform, and otherwise the exponent is
+/// chosen as by [`FullPrecision01`] and values out of the range are rejected.
+///
+/// This is slower than [`UniformFloat`], and is not used by [`Uniform`];
+/// construct it with [`UniformSampler::new`] or
+/// [`UniformSampler::new_inclusive`] and sample with [`Distribution`].
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::uniform::{UniformFullPrecision, UniformSampler};
+///
+/// let range = UniformFullPrecision::new(-1.0f64, 1.0);
+/// let x = rand::thread_rng().sample(range);
+/// assert!(-1.0 <= x && x < 1.0);
+/// ```
+///
+/// [`FullPrecision01`]: crate::distributions::FullPrecision01
+/// [`Distribution`]: crate::distributions::Distribution
+#[derive(Clone, Copy, Debug)]
+pub struct UniformFullPrecision<X> {
+    low: X,
+    // Exclusive; the float after `high` for a closed range, which may be
+    // infinite
+    high: X,
+    mode: FullPrecisionMode,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum FullPrecisionMode {
+    Low,
+    High,
+    Split,
+}
+
+macro_rules! uniform_full_precision_impl {
+    ($ty:ident, $uty:ident, $fraction_bits:expr) => {
+        impl UniformFullPrecision<$ty> {
+            fn create(low: $ty, high: $ty, inclusive: bool) -> Result<Self, UniformError> {
+                if !(low.is_finite() && high.is_finite()) {
+                    return Err(UniformError::NonFinite);
+                }
+                if !(low < high || (inclusive && low == high)) {
+                    return Err(UniformError::EmptyRange);
+                }
+                let high = if !inclusive {
+                    high
+                } else if high > 0.0 {
+                    <$ty>::from_bits(high.to_bits() + 1)
+                } else if high == 0.0 {
+                    <$ty>::from_bits(1)
+                } else {
+                    -<$ty>::from_bits((-high).to_bits() - 1)
+                };
+                // A zero `low` is stored as 0.0 and a zero `high` as -0.0, so
+                // that the bits of `low` and `-high` are those of 0.0.
+                let low = if low == 0.0 { 0.0 } else { low };
+                let high = if high == 0.0 { -0.0 } else { high };
+                let mode = if low >= 0.0 {
+                    FullPrecisionMode::Low
+                } else if high <= 0.0 {
+                    FullPrecisionMode::High
+                } else {
+                    FullPrecisionMode::Split
+                };
+                Ok(UniformFullPrecision { low, high, mode })
+            }
+
+            /// Sample the bits `n` of a float in `[from_bits(a), from_bits(b))`,
+            /// where `a < b` are the bits of non-negative floats, with
+            /// probability proportional to the distance from `n` to the next
+            /// larger float.
+            fn sample_bits<R: Rng + ?Sized>(a: $uty, b: $uty, rng: &mut R) -> $uty {
+                // The distance is `2^(e - 1)` times that of the subnormal
+                // values, where `e >= 1` is the exponent or 1 if subnormal.
+                let exponent = |bits: $uty| core::cmp::max(bits >> $fraction_bits, 1);
+                let (ea, eb) = (exponent(a), exponent(b - 1));
+                if ea == eb {
+                    UniformInt::<$uty>::sample_single(a, b, rng)
+                } else if ea + 1 == eb {
+                    // Values from `c` on are twice as far apart, so each of
+                    // them takes two slots.
+                    let c = eb << $fraction_bits;
+                    let k = UniformInt::<$uty>::sample_single(0, (c - a) + 2 * (b - c), rng);
+                    if k < c - a { a + k } else { c + (k - (c - a)) / 2 }
+                } else {
+                    // The exponent below `eb` is entirely in range, so at
+                    // least a quarter of `[0, 2^eb)` (scaled) is accepted.
+                    loop {
+                        let n = <$ty>::sample_full_precision(rng, eb as u32).to_bits();
+                        if a <= n && n < b {
+                            return n;
+                        }
+                    }
+                }
+            }
+        }
+
+        impl UniformSampler for UniformFullPrecision<$ty> {
+            type X = $ty;
+
+            fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_new(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
+            }
+
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::try_new_inclusive(low_b, high_b)
+                    .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+            }
+
+            fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::create(*low_b.borrow(), *high_b.borrow(), false)
+            }
+
+            fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                Self::create(*low_b.borrow(), *high_b.borrow(), true)
+            }
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                // A negative value `x` is sampled from the bits `n` of the
+                // float before `-x`: the distance from `x` to the next larger
+                // float is that from `n` to the next larger float.
+                let negative = |n: $uty| -<$ty>::from_bits(n + 1);
+                match self.mode {
+                    FullPrecisionMode::Low => {
+                        let n = Self::sample_bits(self.low.to_bits(), self.high.to_bits(), rng);
+                        <$ty>::from_bits(n)
+                    }
+                    FullPrecisionMode::High => {
+                        let (a, b) = ((-self.high).to_bits(), (-self.low).to_bits());
+                        negative(Self::sample_bits(a, b, rng))
+                    }
+                    FullPrecisionMode::Split => {
+                        let (b_pos, b_neg) = (self.high.to_bits(), (-self.low).to_bits());
+                        let top = core::cmp::max(b_pos - 1, b_neg - 1) >> $fraction_bits;
+                        if top == 0 {
+                            // Only subnormal values, which are equally spaced
+                            let k = UniformInt::<$uty>::sample_single(0, b_pos + b_neg, rng);
+                            return if k < b_pos { <$ty>::from_bits(k) } else { negative(k - b_pos) };
+                        }
+                        // Sample from `(-2^t, 2^t)` (scaled), where either
+                        // side has at least the exponents below `top` in range.
+                        loop {
+                            let n = <$ty>::sample_full_precision(rng, top as u32).to_bits();
+                            if rng.gen::<bool>() {
+                                if n < b_pos {
+                                    return <$ty>::from_bits(n);
+                                }
+                            } else if n < b_neg {
+                                return negative(n);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl Distribution<$ty> for UniformFullPrecision<$ty> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                UniformSampler::sample(self, rng)
+            }
+        }
+    };
+}
+
+uniform_full_precision_impl! { f32, u32, 23 }
+uniform_full_precision_impl! { f64, u64, 52 }
+
+
 /// The back-end implementing [`UniformSampler`] for `Duration`.
 ///
 /// Unless you are implementing [`UniformSampler`] for your own types, this type
@@ -867,28 +1434,49 @@ impl UniformSampler for UniformDuration {
 
     #[inline]
     fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self::try_new(low_b, high_b)
+            .unwrap_or_else(|e| panic!("Uniform::new called with {}", e))
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self::try_new_inclusive(low_b, high_b)
+            .unwrap_or_else(|e| panic!("Uniform::new_inclusive called with {}", e))
+    }
+
+    #[inline]
+    fn try_new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
     where
         B1: SampleBorrow<Self::X> + Sized,
         B2: SampleBorrow<Self::X> + Sized,
     {
         let low = *low_b.borrow();
         let high = *high_b.borrow();
-        assert!(low < high, "Uniform::new called with `low >= high`");
-        UniformDuration::new_inclusive(low, high - Duration::new(0, 1))
+        if low >= high {
+            return Err(UniformError::EmptyRange);
+        }
+        UniformDuration::try_new_inclusive(low, high - Duration::new(0, 1))
     }
 
     #[inline]
-    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+    fn try_new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, UniformError>
     where
         B1: SampleBorrow<Self::X> + Sized,
         B2: SampleBorrow<Self::X> + Sized,
     {
         let low = *low_b.borrow();
         let high = *high_b.borrow();
-        assert!(
-            low <= high,
-            "Uniform::new_inclusive called with `low > high`"
-        );
+        if low > high {
+            return Err(UniformError::EmptyRange);
+        }
 
         let low_s = low.as_secs();
         let low_n = low.subsec_nanos();
@@ -925,10 +1513,10 @@ impl UniformSampler for UniformDuration {
                 }
             }
         };
-        UniformDuration {
+        Ok(UniformDuration {
             mode,
             offset: low_n,
-        }
+        })
     }
 
     #[inline]
@@ -962,6 +1550,151 @@ impl UniformSampler for UniformDuration {
     }
 }
 
+/// A back-end implementing [`UniformSampler`] for a newtype `W` wrapping a
+/// type `X` which supports [`Uniform`], such as an integer.
+///
+/// This avoids writing a back-end by hand: given conversions between `W` and
+/// `X`, implementing [`SampleUniform`] for `W` takes a single line.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::uniform::{SampleUniform, UniformNewtype};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// struct UserId(u32);
+///
+/// impl From<u32> for UserId {
+///     fn from(x: u32) -> Self { UserId(x) }
+/// }
+///
+/// impl From<UserId> for u32 {
+///     fn from(id: UserId) -> Self { id.0 }
+/// }
+///
+/// impl SampleUniform for UserId {
+///     type Sampler = UniformNewtype<UserId, u32>;
+/// }
+///
+/// let id = rand::thread_rng().gen_range(UserId(1000), UserId(2000));
+/// assert!(UserId(1000) <= id && id < UserId(2000));
+/// ```
+pub struct UniformNewtype<W, X: SampleUniform> {
+    sampler: X::Sampler,
+    phantom: PhantomData<fn() -> W>,
+}
+
+impl<W, X: SampleUniform> Clone for UniformNewtype<W, X>
+where X::Sampler: Clone
+{
+    fn clone(&self) -> Self {
+        UniformNewtype {
+            sampler: self.sampler.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<W, X: SampleUniform> Copy for UniformNewtype<W, X> where X::Sampler: Copy {}
+
+impl<W, X: SampleUniform> fmt::Debug for UniformNewtype<W, X>
+where X::Sampler: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UniformNewtype")
+            .field("sampler", &self.sampler)
+            .finish()
+    }
+}
+
+impl<W, X> UniformNewtype<W, X>
+where
+    W: Copy + From<X> + Into<X>,
+    X: SampleUniform,
+{
+    fn wrap(sampler: X::Sampler) -> Self {
+        UniformNewtype {
+            sampler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<W, X> UniformSampler for UniformNewtype<W, X>
+where
+    W: Copy + From<X> + Into<X>,
+    X: SampleUniform,
+{
+    type X = W;
+
+    #[inline]
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        Self::wrap(X::Sampler::new(low, high))
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        Self::wrap(X::Sampler::new_inclusive(low, high))
+    }
+
+    #[inline]
+    fn try_new<B1, B2>(low: B1, high: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        X::Sampler::try_new(low, high).map(Self::wrap)
+    }
+
+    #[inline]
+    fn try_new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        X::Sampler::try_new_inclusive(low, high).map(Self::wrap)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        W::from(self.sampler.sample(rng))
+    }
+
+    #[inline]
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Self::X
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        W::from(X::Sampler::sample_single(low, high, rng))
+    }
+
+    #[inline]
+    fn try_sample_single<R: Rng + ?Sized, B1, B2>(
+        low: B1, high: B2, rng: &mut R,
+    ) -> Result<Self::X, UniformError>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high): (X, X) = ((*low.borrow()).into(), (*high.borrow()).into());
+        X::Sampler::try_sample_single(low, high, rng).map(W::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1167,6 +1900,119 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_float_edges() {
+        // For each range, the smallest result is `low` and the largest is in
+        // the range. For a half-open range, `scale` is only decreased from
+        // `high - low` as far as necessary.
+        macro_rules! t {
+            ($ty:ident, $u_scalar:ident, $bits_to_discard:expr) => {{
+                use core::$ty::{EPSILON, MAX, MIN_POSITIVE};
+                let tiny = <$ty>::from_bits(1);
+                let max_rand =
+                    (::core::$u_scalar::MAX >> $bits_to_discard).into_float_with_exponent(0) - 1.0;
+                let v: &[($ty, $ty)] = &[
+                    // subnormal
+                    (0.0, tiny),
+                    (0.0, tiny * 3.0),
+                    (tiny, tiny * 2.0),
+                    (-tiny, tiny),
+                    (-tiny * 7.0, -tiny * 2.0),
+                    (tiny * 5.0, MIN_POSITIVE),
+                    (-MIN_POSITIVE, MIN_POSITIVE),
+                    (MIN_POSITIVE / 3.0, MIN_POSITIVE * 3.0),
+                    // huge
+                    (MAX / 2.0, MAX),
+                    (MAX * (1.0 - EPSILON), MAX),
+                    (0.0, MAX),
+                    (-MAX, 0.0),
+                    (-MAX, -MAX / 3.0),
+                    (-MAX, MAX),
+                    (-MAX * 0.6, MAX * 0.7),
+                    (-MAX, MAX * 1e-10),
+                    (-1.0, MAX),
+                    // negative and spanning zero
+                    (-1.0, 1.0),
+                    (-1.0, 1e-30),
+                    (-1e30, 1.0),
+                    (-3.0, -1.0),
+                    (-1.0, 0.0),
+                    (-1.0, -0.0),
+                    (-1.0 - 2.0 * EPSILON, -1.0),
+                    (-1e-30, 7e-31),
+                    // narrow
+                    (1.0, 1.0 + EPSILON),
+                    (100.0, 100.0 + 100.0 * EPSILON),
+                    (0.0, 1.0),
+                    (1.0, 2.0),
+                ];
+                let mut rng = crate::test::rng(265);
+                for &(low, high) in v.iter() {
+                    let mut zero_rng = StepRng::new(0, 0);
+                    let mut max_rng = StepRng::new(!0, 0);
+
+                    let d = UniformFloat::<$ty>::new(low, high);
+                    assert_eq!(d.sample(&mut zero_rng), low);
+                    let max = d.sample(&mut max_rng);
+                    assert!(low <= max && max < high, "[{:e}, {:e}): {:e}", low, high, max);
+                    let factor = d.factor.unwrap_or(1.0);
+                    let scale = <$ty>::from_bits(d.scale.to_bits() + 1);
+                    assert!(
+                        d.scale == high / factor - d.low
+                            || (max_rand * scale + d.low) * factor >= high,
+                        "[{:e}, {:e}): scale {:e} not maximal", low, high, d.scale
+                    );
+
+                    let d = UniformFloat::<$ty>::new_inclusive(low, high);
+                    assert_eq!(d.sample(&mut zero_rng), low);
+                    let max = d.sample(&mut max_rng);
+                    assert!(low <= max && max <= high, "[{:e}, {:e}]: {:e}", low, high, max);
+
+                    for _ in 0..100 {
+                        let x = UniformFloat::<$ty>::sample_single(low, high, &mut rng);
+                        assert!(low <= x && x < high, "[{:e}, {:e}): {:e}", low, high, x);
+                    }
+                }
+
+                // When `high - low` overflows, the whole range is used.
+                let max = UniformFloat::<$ty>::new(-MAX, MAX).sample(&mut StepRng::new(!0, 0));
+                assert!(max > MAX * 0.99);
+                let max = (0..100)
+                    .map(|_| UniformFloat::<$ty>::sample_single(-MAX, MAX, &mut rng))
+                    .fold(-MAX, |a, b| if a < b { b } else { a });
+                assert!(max > MAX * 0.5);
+            }};
+        }
+        t!(f32, u32, 32 - 23);
+        t!(f64, u64, 64 - 52);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_float_exhaustive() {
+        // All fractions of an `f32` on small ranges
+        let tiny = f32::from_bits(1);
+        let v: &[(f32, f32)] = &[
+            (0.0, tiny * 3.0),
+            (-tiny * 2.0, tiny * 2.0),
+            (1.0, 1.0 + 2.0 * ::core::f32::EPSILON),
+            (-1.0 - 2.0 * ::core::f32::EPSILON, -1.0),
+        ];
+        for &(low, high) in v.iter() {
+            let d = UniformFloat::<f32>::new(low, high);
+            let d_inclusive = UniformFloat::<f32>::new_inclusive(low, high);
+            let mut rng = StepRng::new(0, 1 << 9);
+            for _ in 0..(1 << 23) {
+                let x = d.sample(&mut rng);
+                assert!(low <= x && x < high, "[{:e}, {:e}): {:e}", low, high, x);
+            }
+            for _ in 0..(1 << 23) {
+                let x = d_inclusive.sample(&mut rng);
+                assert!(low <= x && x <= high, "[{:e}, {:e}]: {:e}", low, high, x);
+            }
+        }
+    }
+
     #[test]
     #[cfg(all(
         feature = "std",
@@ -1228,6 +2074,190 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_new() {
+        use core::time::Duration;
+        use core::{f32, f64};
+        let mut rng = crate::test::rng(254);
+
+        assert!(Uniform::try_new(10, 11).is_ok());
+        assert!(Uniform::try_new_inclusive(10, 10).is_ok());
+        assert_eq!(Uniform::try_new(10, 10).unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(Uniform::try_new(10u8, 5).unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(Uniform::try_new_inclusive(10, 9).unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(
+            UniformInt::<u64>::try_sample_single(3, 3, &mut rng),
+            Err(UniformError::EmptyRange)
+        );
+
+        let (low, high) = (Duration::new(1, 0), Duration::new(2, 0));
+        assert!(Uniform::try_new(low, high).is_ok());
+        assert!(Uniform::try_new_inclusive(low, low).is_ok());
+        assert_eq!(Uniform::try_new(low, low).unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(Uniform::try_new_inclusive(high, low).unwrap_err(), UniformError::EmptyRange);
+
+        macro_rules! t {
+            ($ty:ident, $f_scalar:ident) => {{
+                use super::UniformError::*;
+                let v: &[($f_scalar, $f_scalar, UniformError)] = &[
+                    ($f_scalar::NAN, 0.0, NonFinite),
+                    (1.0, $f_scalar::NAN, NonFinite),
+                    (1.0, 0.5, EmptyRange),
+                    ($f_scalar::MAX, -$f_scalar::MAX, EmptyRange),
+                    ($f_scalar::INFINITY, $f_scalar::INFINITY, NonFinite),
+                    ($f_scalar::NEG_INFINITY, 5.0, NonFinite),
+                    (5.0, $f_scalar::INFINITY, NonFinite),
+                    ($f_scalar::NEG_INFINITY, $f_scalar::INFINITY, NonFinite),
+                ];
+                for &(low_scalar, high_scalar, err) in v.iter() {
+                    for lane in 0..<$ty>::lanes() {
+                        let low = <$ty>::splat(0.0 as $f_scalar).replace(lane, low_scalar);
+                        let high = <$ty>::splat(1.0 as $f_scalar).replace(lane, high_scalar);
+                        assert_eq!(Uniform::try_new(low, high).unwrap_err(), err);
+                        assert_eq!(Uniform::try_new_inclusive(low, high).unwrap_err(), err);
+                        assert_eq!(
+                            UniformFloat::<$ty>::try_sample_single(low, high, &mut rng),
+                            Err(err)
+                        );
+                    }
+                }
+                let (low, high) = (<$ty>::splat(1.0), <$ty>::splat(2.0));
+                assert!(Uniform::try_new(low, high).is_ok());
+                assert!(Uniform::try_new_inclusive(low, low).is_ok());
+                assert_eq!(Uniform::try_new(low, low).unwrap_err(), EmptyRange);
+            }};
+        }
+
+        t!(f32, f32);
+        t!(f64, f64);
+        #[cfg(feature = "simd_support")]
+        {
+            t!(f32x4, f32);
+            t!(f64x2, f64);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_full_precision_bounds() {
+        let mut rng = crate::test::rng(255);
+        macro_rules! t {
+            ($f_scalar:ident) => {{
+                use core::$f_scalar::{MAX, MIN_POSITIVE};
+                let tiny = <$f_scalar>::from_bits(1);
+                let v: &[($f_scalar, $f_scalar)] = &[
+                    (0.0, 1.0),
+                    (-1.0, 1.0),
+                    (-3.0, 1e-30),
+                    (-1e-30, 0.0),
+                    (1.0, 1.0 + ::core::$f_scalar::EPSILON),
+                    (0.0, tiny),
+                    (-tiny, tiny),
+                    (-tiny, -0.0),
+                    (-0.0, MIN_POSITIVE),
+                    (tiny, MIN_POSITIVE),
+                    (-MAX, MAX),
+                    (-MAX, -MAX / 3.0),
+                    (MAX / 3.0, MAX),
+                ];
+                for &(low, high) in v.iter() {
+                    let d = UniformFullPrecision::<$f_scalar>::new(low, high);
+                    let d_inclusive = UniformFullPrecision::<$f_scalar>::new_inclusive(low, high);
+                    for _ in 0..1000 {
+                        let x = rng.sample(d);
+                        assert!(low <= x && x < high, "{} not in [{}, {})", x, low, high);
+                        let x = rng.sample(d_inclusive);
+                        assert!(low <= x && x <= high, "{} not in [{}, {}]", x, low, high);
+                    }
+                }
+
+                for &x in [-2.5, -tiny, 0.0, MAX].iter() {
+                    let d = UniformFullPrecision::<$f_scalar>::new_inclusive(x, x);
+                    assert_eq!(rng.sample(d), x);
+                }
+
+                let nan = ::core::$f_scalar::NAN;
+                let inf = ::core::$f_scalar::INFINITY;
+                type U = UniformFullPrecision<$f_scalar>;
+                assert_eq!(U::try_new(1.0, 1.0).unwrap_err(), UniformError::EmptyRange);
+                assert_eq!(U::try_new_inclusive(1.0, 0.5).unwrap_err(), UniformError::EmptyRange);
+                assert_eq!(U::try_new(0.0, inf).unwrap_err(), UniformError::NonFinite);
+                assert_eq!(U::try_new_inclusive(nan, 0.0).unwrap_err(), UniformError::NonFinite);
+            }};
+        }
+        t!(f32);
+        t!(f64);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_full_precision_tail() {
+        // Unlike with `UniformFloat`, the fraction bits of values close to
+        // zero are all random: of the values within `width / 16` of zero,
+        // half are odd.
+        const N: u32 = 100_000;
+        macro_rules! t {
+            ($f_scalar:ident, $low:expr, $high:expr, $seed:expr) => {{
+                let (low, high): ($f_scalar, $f_scalar) = ($low, $high);
+                let width = if low < 0.0 { -low } else { high };
+                let d = UniformFullPrecision::new(low, high);
+                let mut rng = crate::test::rng($seed);
+                let (mut tiny, mut odd) = (0, 0);
+                for _ in 0..N {
+                    let x: $f_scalar = rng.sample(d);
+                    if x.abs() < width / 16.0 {
+                        tiny += 1;
+                        odd += (x.to_bits() & 1) as u32;
+                    }
+                }
+                let what = format!("[{}, {})", low, high);
+                crate::test::assert_binomial(tiny, N, 1.0 / 16.0, &what);
+                crate::test::assert_binomial(odd, tiny, 0.5, &what);
+            }};
+        }
+        t!(f32, 0.0, 3.0, 256);
+        t!(f32, -1.0, 1.0, 257);
+        t!(f32, -(2.0f32).powi(-100) * 5.0, 0.0, 258);
+        t!(f64, 0.0, 1.0, 259);
+        t!(f64, -3.0, 3.0, 260);
+        t!(f64, -3.5, 0.0, 261);
+
+        // Tiny values are possible; here the fraction is 5, and there are
+        // 12 + 64 leading zero bits.
+        let mut deep = StepRng::new(5, 5u64.wrapping_neg());
+        let x: f64 = deep.sample(UniformFullPrecision::new(0.0, 1.0));
+        assert_eq!(x, f64::from_bits((1023 - 77) << 52 | 5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_full_precision_mass() {
+        // Each value is sampled with probability proportional to the distance
+        // to the next larger float.
+        const N: u32 = 10_000;
+        use core::f64::EPSILON;
+        let tiny = f64::from_bits(1);
+        let mut rng = crate::test::rng(262);
+        // Counts the samples of `d` in `[a, b]`
+        let mut check = |d: UniformFullPrecision<f64>, a: f64, b: f64, p: f64| {
+            let count = (0..N)
+                .map(|_| rng.sample(d))
+                .filter(|&x| a <= x && x <= b)
+                .count() as u32;
+            crate::test::assert_binomial(count, N, p, &format!("{:?}: [{}, {}]", d, a, b));
+        };
+        // Values from 1 on are twice as far apart as those below 1
+        check(UniformFullPrecision::new(1.0 - EPSILON / 2.0, 1.0 + EPSILON), 1.0, 1.0, 2.0 / 3.0);
+        check(UniformFullPrecision::new_inclusive(1.0, 1.0 + EPSILON), 1.0, 1.0, 0.5);
+        check(UniformFullPrecision::new_inclusive(-1.0, -1.0 + EPSILON / 2.0), -1.0, -1.0, 0.5);
+        check(UniformFullPrecision::new_inclusive(-tiny, 0.0), 0.0, 0.0, 0.5);
+        check(UniformFullPrecision::new(-3.0 * tiny, 2.0 * tiny), -tiny, -tiny, 0.2);
+        check(UniformFullPrecision::new(0.0, 3.0), 2.0, 3.0, 1.0 / 3.0);
+        check(UniformFullPrecision::new(-5.0, 3.0), 2.0, 3.0, 1.0 / 8.0);
+        check(UniformFullPrecision::new(-5.0, 3.0), -5.0, -3.0, 1.0 / 4.0);
+    }
 
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
@@ -1303,6 +2333,74 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_char() {
+        let mut rng = crate::test::rng(891);
+        let mut max = core::char::from_u32(0).unwrap();
+        for _ in 0..100 {
+            let c = rng.gen_range('A', 'z');
+            assert!('A' <= c && c < 'z');
+            max = max.max(c);
+        }
+        assert_eq!(max, 'y');
+
+        // Only the two code points around the surrogate gap
+        let d = Uniform::new_inclusive('\u{D7FF}', '\u{E000}');
+        let mut seen = [false; 2];
+        for _ in 0..100 {
+            let c = rng.sample(d);
+            assert!(c == '\u{D7FF}' || c == '\u{E000}');
+            seen[(c == '\u{E000}') as usize] = true;
+        }
+        assert_eq!(seen, [true, true]);
+        assert_eq!(rng.gen_range('\u{D7FF}', '\u{E000}'), '\u{D7FF}');
+
+        let d = Uniform::new_inclusive('\u{0}', core::char::MAX);
+        for _ in 0..100 {
+            let c = rng.sample(d);
+            assert!(core::char::from_u32(c as u32).is_some());
+        }
+
+        assert_eq!(Uniform::try_new('b', 'a').unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(rng.try_gen_range('a', 'a'), Err(UniformError::EmptyRange));
+        assert!(Uniform::try_new_inclusive('a', 'a').is_ok());
+    }
+
+    #[test]
+    fn test_newtype() {
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+        struct Id(u16);
+        impl From<u16> for Id {
+            fn from(x: u16) -> Self {
+                Id(x)
+            }
+        }
+        impl From<Id> for u16 {
+            fn from(id: Id) -> Self {
+                id.0
+            }
+        }
+        impl SampleUniform for Id {
+            type Sampler = UniformNewtype<Id, u16>;
+        }
+
+        let mut rng = crate::test::rng(892);
+        let (low, high) = (Id(100), Id(200));
+        let d = Uniform::new(low, high);
+        let d_inclusive = Uniform::new_inclusive(&low, &high);
+        for _ in 0..100 {
+            let x = rng.sample(d);
+            assert!(low <= x && x < high);
+            let x = rng.sample(d_inclusive);
+            assert!(low <= x && x <= high);
+            let x = rng.gen_range(low, high);
+            assert!(low <= x && x < high);
+        }
+        assert_eq!(Uniform::try_new(high, low).unwrap_err(), UniformError::EmptyRange);
+        assert_eq!(rng.try_gen_range(low, low), Err(UniformError::EmptyRange));
+    }
+
     #[test]
     fn test_uniform_from_std_range() {
         let r = Uniform::from(2u32..7);
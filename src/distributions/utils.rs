@@ -225,13 +225,10 @@ pub(crate) trait FloatSIMDUtils {
 
     type Mask;
     fn finite_mask(self) -> Self::Mask;
-    fn gt_mask(self, other: Self) -> Self::Mask;
-    fn ge_mask(self, other: Self) -> Self::Mask;
 
-    // Decrease all lanes where the mask is `true` to the next lower value
-    // representable by the floating-point type. At least one of the lanes
-    // must be set.
-    fn decrease_masked(self, mask: Self::Mask) -> Self;
+    // Take the lanes of `self` where the mask is `true`, and of `other`
+    // elsewhere.
+    fn select(self, mask: Self::Mask, other: Self) -> Self;
 
     // Convert from int value. Conversion is done while retaining the numerical
     // value, not by retaining the binary representation.
@@ -269,29 +266,6 @@ pub(crate) trait FloatAsSIMD: Sized {
     }
 }
 
-pub(crate) trait BoolAsSIMD: Sized {
-    fn any(self) -> bool;
-    fn all(self) -> bool;
-    fn none(self) -> bool;
-}
-
-impl BoolAsSIMD for bool {
-    #[inline(always)]
-    fn any(self) -> bool {
-        self
-    }
-
-    #[inline(always)]
-    fn all(self) -> bool {
-        self
-    }
-
-    #[inline(always)]
-    fn none(self) -> bool {
-        !self
-    }
-}
-
 macro_rules! scalar_float_impl {
     ($ty:ident, $uty:ident) => {
         #[cfg(not(std))]
@@ -337,19 +311,8 @@ macro_rules! scalar_float_impl {
             }
 
             #[inline(always)]
-            fn gt_mask(self, other: Self) -> Self::Mask {
-                self > other
-            }
-
-            #[inline(always)]
-            fn ge_mask(self, other: Self) -> Self::Mask {
-                self >= other
-            }
-
-            #[inline(always)]
-            fn decrease_masked(self, mask: Self::Mask) -> Self {
-                debug_assert!(mask, "At least one lane must be set");
-                <$ty>::from_bits(self.to_bits() - 1)
+            fn select(self, mask: Self::Mask, other: Self) -> Self {
+                if mask { self } else { other }
             }
 
             #[inline]
@@ -397,25 +360,8 @@ macro_rules! simd_impl {
             }
 
             #[inline(always)]
-            fn gt_mask(self, other: Self) -> Self::Mask {
-                self.gt(other)
-            }
-
-            #[inline(always)]
-            fn ge_mask(self, other: Self) -> Self::Mask {
-                self.ge(other)
-            }
-
-            #[inline(always)]
-            fn decrease_masked(self, mask: Self::Mask) -> Self {
-                // Casting a mask into ints will produce all bits set for
-                // true, and 0 for false. Adding that to the binary
-                // representation of a float means subtracting one from
-                // the binary representation, resulting in the next lower
-                // value representable by $ty. This works even when the
-                // current value is infinity.
-                debug_assert!(mask.any(), "At least one lane must be set");
-                <$ty>::from_bits(<$uty>::from_bits(self) + <$uty>::from_bits(mask))
+            fn select(self, mask: Self::Mask, other: Self) -> Self {
+                mask.select(self, other)
             }
 
             #[inline]
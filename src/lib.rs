@@ -91,6 +91,9 @@ macro_rules! error { ($($x:tt)*) => (
 
 // Re-exports from rand_core
 pub use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+#[cfg(feature = "async")] pub use rand_core::AsyncRngCore;
+#[cfg(feature = "std")]
+pub use rand_core::{reset_entropy_source, set_entropy_source};
 
 // Public modules
 pub mod distributions;
@@ -101,7 +104,7 @@ pub mod seq;
 
 // Public exports
 #[cfg(all(feature = "std", feature = "std_rng"))]
-pub use crate::rngs::thread::thread_rng;
+pub use crate::rngs::thread::{thread_rng, try_thread_rng};
 pub use rng::{Fill, Rng};
 
 #[cfg(all(feature = "std", feature = "std_rng"))]
@@ -168,6 +171,46 @@ mod test {
         rand_pcg::Pcg32::new(seed, INC)
     }
 
+    /// An RNG which always fails, with the error code [`FailingRng::CODE`].
+    #[cfg(feature = "std")]
+    pub struct FailingRng;
+
+    #[cfg(feature = "std")]
+    impl FailingRng {
+        pub const CODE: u32 = Error::CUSTOM_START + 7;
+    }
+
+    #[cfg(feature = "std")]
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.try_fill_bytes(dest).unwrap_or_else(|err| panic!("FailingRng: {}", err))
+        }
+
+        fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Error> {
+            Err(core::num::NonZeroU32::new(Self::CODE).unwrap().into())
+        }
+    }
+
+    /// Assert that `count` successes in `n` trials are consistent with a
+    /// success probability of `p`, within five standard deviations.
+    #[cfg(feature = "std")]
+    pub fn assert_binomial(count: u32, n: u32, p: f64, what: &str) {
+        let expected = f64::from(n) * p;
+        let sigma = (expected * (1.0 - p)).sqrt();
+        assert!(
+            (f64::from(count) - expected).abs() <= 5.0 * sigma + 1.0,
+            "{}: {} of {}, expected {}", what, count, n, expected
+        );
+    }
+
     #[test]
     #[cfg(all(feature = "std", feature = "std_rng"))]
     fn test_random() {
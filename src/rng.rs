@@ -10,7 +10,7 @@
 //! [`Rng`] trait
 
 use rand_core::{Error, RngCore};
-use crate::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use crate::distributions::uniform::{SampleBorrow, SampleUniform, UniformError, UniformSampler};
 use crate::distributions::{self, Distribution, Standard};
 use core::num::Wrapping;
 use core::{mem, slice};
@@ -102,7 +102,8 @@ pub trait Rng: RngCore {
     ///
     /// # Panics
     ///
-    /// Panics if `low >= high`.
+    /// Panics if `low >= high`, or if a floating-point bound is not finite.
+    /// See [`try_gen_range`] for a variant returning an error instead.
     ///
     /// # Example
     ///
@@ -117,6 +118,7 @@ pub trait Rng: RngCore {
     /// ```
     ///
     /// [`Uniform`]: distributions::uniform::Uniform
+    /// [`try_gen_range`]: Rng::try_gen_range
     fn gen_range<T: SampleUniform, B1, B2>(&mut self, low: B1, high: B2) -> T
     where
         B1: SampleBorrow<T> + Sized,
@@ -125,6 +127,37 @@ pub trait Rng: RngCore {
         T::Sampler::sample_single(low, high, self)
     }
 
+    /// Generate a random value in the range [`low`, `high`), or return an
+    /// error if the range is empty or, for floating-point types, a bound is
+    /// not finite.
+    ///
+    /// This is the non-panicking variant of [`gen_range`], for ranges which
+    /// are not known to be valid, e.g. because they come from user input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::uniform::UniformError;
+    ///
+    /// let mut rng = thread_rng();
+    /// let n: u32 = rng.try_gen_range(0, 10).unwrap();
+    /// assert!(n < 10);
+    /// assert_eq!(rng.try_gen_range::<u32, _, _>(10, 10), Err(UniformError::EmptyRange));
+    /// assert_eq!(rng.try_gen_range(0.0, std::f64::INFINITY), Err(UniformError::NonFinite));
+    /// ```
+    ///
+    /// [`gen_range`]: Rng::gen_range
+    fn try_gen_range<T: SampleUniform, B1, B2>(
+        &mut self, low: B1, high: B2,
+    ) -> Result<T, UniformError>
+    where
+        B1: SampleBorrow<T> + Sized,
+        B2: SampleBorrow<T> + Sized,
+    {
+        T::Sampler::try_sample_single(low, high, self)
+    }
+
     /// Sample a new value, using the given distribution.
     ///
     /// ### Example
@@ -506,6 +539,26 @@ mod test {
         r.gen_range(5, 2);
     }
 
+    #[test]
+    fn test_try_gen_range() {
+        use crate::distributions::uniform::UniformError;
+        let mut r = rng(104);
+        for _ in 0..1000 {
+            let a = r.try_gen_range(-4711, 17).unwrap();
+            assert!(a >= -4711 && a < 17);
+            let a = r.try_gen_range(&1.5f64, 2.5).unwrap();
+            assert!(a >= 1.5 && a < 2.5);
+        }
+        assert_eq!(r.try_gen_range(5, -2), Err(UniformError::EmptyRange));
+        assert_eq!(r.try_gen_range(5usize, 5), Err(UniformError::EmptyRange));
+        assert_eq!(r.try_gen_range(2.0, 1.0), Err(UniformError::EmptyRange));
+        let inf = ::core::f32::INFINITY;
+        assert_eq!(r.try_gen_range(0.0, inf), Err(UniformError::NonFinite));
+        assert_eq!(r.try_gen_range(-inf, 0.0), Err(UniformError::NonFinite));
+        let nan = ::core::f32::NAN;
+        assert_eq!(r.try_gen_range(nan, 1.0), Err(UniformError::NonFinite));
+    }
+
     #[test]
     fn test_gen_bool() {
         let mut r = rng(105);
@@ -0,0 +1,215 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper around any asynchronous byte stream to treat it as an
+//! [`AsyncRngCore`].
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use rand_core::{AsyncRngCore, Error};
+
+use super::read::ReadError;
+
+/// Reading bytes asynchronously.
+///
+/// This has the same signature as `AsyncRead` of the `futures` crate (and,
+/// apart from the buffer type, of `tokio`), so that a byte stream from either
+/// can be used with [`AsyncReadRng`] through a trivial wrapper.
+pub trait AsyncRead {
+    /// Attempt to read into `buf`, returning the number of bytes read, where
+    /// 0 means the end of the stream.
+    ///
+    /// If no data is available yet, returns `Poll::Pending` and arranges for
+    /// the current task to be woken when data may be available.
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+}
+
+/// An in-memory stream, which is always ready.
+impl AsyncRead for &[u8] {
+    fn poll_read(
+        self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(io::Read::read(self.get_mut(), buf))
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for &mut R {
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_read(cx, buf)
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for Box<R> {
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_read(cx, buf)
+    }
+}
+
+/// An asynchronous source of random bytes reading from any type
+/// implementing [`AsyncRead`], such as a socket to an entropy daemon.
+///
+/// This is the asynchronous counterpart of [`ReadRng`]: interrupted reads
+/// are retried, and all other errors, including when the stream ends, are
+/// reported as a [`ReadError`].
+///
+/// Requires the `async` feature.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(dead_code)]
+/// use rand::rngs::adapter::{AsyncRead, AsyncReadRng};
+/// use rand::rngs::StdRng;
+/// use rand::AsyncRngCore;
+///
+/// async fn seed_from_daemon<S: AsyncRead + Unpin>(socket: S)
+///     -> Result<StdRng, rand::Error>
+/// {
+///     AsyncReadRng::new(socket).seed_rng::<StdRng>().await
+/// }
+/// ```
+///
+/// [`ReadRng`]: crate::rngs::adapter::ReadRng
+#[derive(Debug)]
+pub struct AsyncReadRng<R> {
+    reader: R,
+}
+
+impl<R: AsyncRead + Unpin> AsyncReadRng<R> {
+    /// Create a new `AsyncReadRng` from an `AsyncRead`.
+    pub fn new(r: R) -> AsyncReadRng<R> {
+        AsyncReadRng { reader: r }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRngCore for AsyncReadRng<R> {
+    fn poll_fill_bytes(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, dest: &mut [u8],
+    ) -> Poll<Result<usize, Error>> {
+        if dest.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let reader = &mut self.get_mut().reader;
+        loop {
+            return match Pin::new(&mut *reader).poll_read(cx, dest) {
+                Poll::Ready(Ok(0)) => {
+                    let err = io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    );
+                    Poll::Ready(Err(Error::new(ReadError(err))))
+                }
+                Poll::Ready(Ok(n)) => Poll::Ready(Ok(n)),
+                Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => Poll::Ready(Err(Error::new(ReadError(e)))),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    use super::{AsyncRead, AsyncReadRng};
+    use crate::rngs::adapter::ReadError;
+    use crate::{AsyncRngCore, RngCore, SeedableRng};
+    use rand_pcg::Pcg32;
+
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// An in-memory stream returning one byte per read, alternating with
+    /// `Pending` and interrupts.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        calls: usize,
+    }
+
+    impl<'a> AsyncRead for Trickle<'a> {
+        fn poll_read(
+            self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            this.calls += 1;
+            match this.calls % 3 {
+                0 => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                1 => Poll::Ready(Err(io::ErrorKind::Interrupted.into())),
+                _ => Pin::new(&mut this.data).poll_read(cx, &mut buf[..1]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_reader_rng_fill_bytes() {
+        let v = [1u8, 2, 3, 4, 5, 6, 7, 8];
+        let mut w = [0u8; 8];
+
+        let mut rng = AsyncReadRng::new(&v[..]);
+        block_on(rng.fill_bytes(&mut w)).unwrap();
+        assert_eq!(v, w);
+
+        let mut w = [0u8; 8];
+        let mut rng = AsyncReadRng::new(Trickle { data: &v[..], calls: 0 });
+        block_on(rng.fill_bytes(&mut w)).unwrap();
+        assert_eq!(v, w);
+    }
+
+    #[test]
+    fn test_async_reader_rng_seed() {
+        let v = [7u8; 16];
+        let mut rng = AsyncReadRng::new(Trickle { data: &v[..], calls: 0 });
+        let mut seeded: Pcg32 = block_on(rng.seed_rng()).unwrap();
+        assert_eq!(seeded.next_u64(), Pcg32::from_seed(v).next_u64());
+    }
+
+    #[test]
+    fn test_async_reader_rng_insufficient_bytes() {
+        let v = [1u8, 2, 3, 4, 5, 6, 7, 8];
+        let mut w = [0u8; 9];
+
+        let mut rng = AsyncReadRng::new(Trickle { data: &v[..], calls: 0 });
+        let result = block_on(rng.fill_bytes(&mut w));
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        let err = err.inner().downcast_ref::<ReadError>().unwrap();
+        assert!(err.to_string().contains("failed to fill whole buffer"));
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper running continuous health tests on an entropy source.
+
+use core::num::NonZeroU32;
+
+use rand_core::{impls, Error, RngCore};
+
+/// Error code reported by [`HealthTestRng`] when the Repetition Count Test
+/// fails.
+pub const REPETITION_COUNT_FAILURE: u32 = Error::INTERNAL_START + 0x1_0000;
+
+/// Error code reported by [`HealthTestRng`] when the Adaptive Proportion Test
+/// fails.
+pub const ADAPTIVE_PROPORTION_FAILURE: u32 = Error::INTERNAL_START + 0x1_0001;
+
+// Window size of the Adaptive Proportion Test for non-binary samples.
+const APT_WINDOW: u32 = 512;
+
+// The false positive probability of both tests is 2^-ALPHA_LOG2.
+const ALPHA_LOG2: f64 = 40.0;
+
+/// An entropy source adapter running the continuous health tests of
+/// [NIST SP 800-90B], section 4.4, on the bytes produced by another RNG.
+///
+/// Each output byte is a sample for the Repetition Count Test (which detects
+/// a source stuck on a single value) and for the Adaptive Proportion Test
+/// (which detects a large loss of entropy, by counting how often the first
+/// sample of each window of 512 samples recurs in that window). The cutoffs
+/// of both tests are derived from the min-entropy per byte claimed for the
+/// source, with a false positive probability of 2<sup>-40</sup> per sample,
+/// or can be given directly with [`HealthTestRng::with_cutoffs`].
+///
+/// When a test fails, [`try_fill_bytes`] returns an error with the code
+/// [`REPETITION_COUNT_FAILURE`] or [`ADAPTIVE_PROPORTION_FAILURE`], and
+/// keeps doing so (without using the wrapped source) until
+/// [`HealthTestRng::reset`] is called. The other [`RngCore`] methods panic
+/// on failure. Errors of the wrapped source are passed on unchanged.
+///
+/// The start-up tests of SP 800-90B are not performed; to approximate them,
+/// discard the first 1024 bytes produced.
+///
+/// # Example
+///
+/// ```
+/// use rand::RngCore;
+/// use rand::rngs::OsRng;
+/// use rand::rngs::adapter::HealthTestRng;
+///
+/// let mut rng = HealthTestRng::new(OsRng, 8.0);
+/// let mut seed = [0u8; 32];
+/// rng.try_fill_bytes(&mut seed).expect("entropy source failed");
+/// ```
+///
+/// [NIST SP 800-90B]: https://csrc.nist.gov/publications/detail/sp/800-90b/final
+/// [`try_fill_bytes`]: RngCore::try_fill_bytes
+#[derive(Debug, Clone)]
+pub struct HealthTestRng<R> {
+    inner: R,
+    repetition_cutoff: u32,
+    proportion_cutoff: u32,
+    // Repetition Count Test state
+    last: u8,
+    repetitions: u32,
+    // Adaptive Proportion Test state
+    reference: u8,
+    occurrences: u32,
+    window_index: u32,
+    failure: Option<NonZeroU32>,
+}
+
+impl<R: RngCore> HealthTestRng<R> {
+    /// Create a new `HealthTestRng`, for a source with the given min-entropy
+    /// per byte.
+    ///
+    /// The min-entropy should be the (conservative) assessed entropy of the
+    /// source; `8.0` claims full entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_entropy` is not in the range `(0, 8]`.
+    pub fn new(inner: R, min_entropy: f64) -> Self {
+        assert!(min_entropy > 0.0 && min_entropy <= 8.0,
+                "HealthTestRng::new called with min_entropy outside (0, 8]");
+        Self::with_cutoffs(inner, repetition_cutoff(min_entropy), proportion_cutoff(min_entropy))
+    }
+
+    /// Create a new `HealthTestRng` with the given cutoffs.
+    ///
+    /// The Repetition Count Test fails when a byte is repeated
+    /// `repetition_cutoff` times in a row, and the Adaptive Proportion Test
+    /// fails when the first byte of a window of 512 bytes occurs
+    /// `proportion_cutoff` times within the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cutoff is less than 2.
+    pub fn with_cutoffs(inner: R, repetition_cutoff: u32, proportion_cutoff: u32) -> Self {
+        assert!(repetition_cutoff >= 2 && proportion_cutoff >= 2,
+                "HealthTestRng::with_cutoffs called with cutoff < 2");
+        HealthTestRng {
+            inner,
+            repetition_cutoff,
+            proportion_cutoff,
+            last: 0,
+            repetitions: 0,
+            reference: 0,
+            occurrences: 0,
+            window_index: 0,
+            failure: None,
+        }
+    }
+
+    /// Clear a failure and restart both tests.
+    pub fn reset(&mut self) {
+        self.repetitions = 0;
+        self.window_index = 0;
+        self.failure = None;
+    }
+
+    /// Run both tests on a sample.
+    #[inline]
+    fn test(&mut self, sample: u8) -> Result<(), NonZeroU32> {
+        if self.repetitions > 0 && sample == self.last {
+            self.repetitions += 1;
+            if self.repetitions >= self.repetition_cutoff {
+                return Err(NonZeroU32::new(REPETITION_COUNT_FAILURE).unwrap());
+            }
+        } else {
+            self.last = sample;
+            self.repetitions = 1;
+        }
+
+        if self.window_index == 0 {
+            self.reference = sample;
+            self.occurrences = 1;
+        } else if sample == self.reference {
+            self.occurrences += 1;
+            if self.occurrences >= self.proportion_cutoff {
+                return Err(NonZeroU32::new(ADAPTIVE_PROPORTION_FAILURE).unwrap());
+            }
+        }
+        self.window_index += 1;
+        if self.window_index == APT_WINDOW {
+            self.window_index = 0;
+        }
+        Ok(())
+    }
+}
+
+/// The Repetition Count Test cutoff, `1 + ceil(-log2(alpha) / H)`.
+fn repetition_cutoff(min_entropy: f64) -> u32 {
+    1 + (ALPHA_LOG2 / min_entropy).ceil() as u32
+}
+
+/// The Adaptive Proportion Test cutoff, `1 + CRITBINOM(W, 2^-H, 1 - alpha)`,
+/// i.e. one more than the smallest `k` such that `P(X > k) <= alpha` for
+/// `X ~ Binomial(W, 2^-H)`.
+fn proportion_cutoff(min_entropy: f64) -> u32 {
+    critical_value(min_entropy, ALPHA_LOG2) + 1
+}
+
+fn critical_value(min_entropy: f64, alpha_log2: f64) -> u32 {
+    let n = APT_WINDOW as usize;
+    let p = (-min_entropy).exp2();
+    let alpha = (-alpha_log2).exp2();
+
+    // Compute the probability mass function in logarithmic space to avoid
+    // underflow for small min-entropies.
+    let mut pmf = [0f64; APT_WINDOW as usize + 1];
+    let mut ln_pmf = (n as f64) * (-p).ln_1p();
+    let ln_odds = p.ln() - (-p).ln_1p();
+    for (k, x) in pmf.iter_mut().enumerate() {
+        *x = ln_pmf.exp();
+        ln_pmf += (((n - k) as f64) / ((k + 1) as f64)).ln() + ln_odds;
+    }
+
+    let mut k = n;
+    let mut tail = 0.0; // P(X > k)
+    while k > 0 && tail + pmf[k] <= alpha {
+        tail += pmf[k];
+        k -= 1;
+    }
+    k as u32
+}
+
+impl<R: RngCore> RngCore for HealthTestRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap_or_else(|err| {
+            panic!("entropy source failed: {}", err)
+        });
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if let Some(code) = self.failure {
+            return Err(Error::from(code));
+        }
+        self.inner.try_fill_bytes(dest)?;
+        for &sample in dest.iter() {
+            if let Err(code) = self.test(sample) {
+                self.failure = Some(code);
+                return Err(Error::from(code));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    fn failure_code(rng: &mut HealthTestRng<StepRng>, len: usize) -> Option<u32> {
+        let mut buf = vec![0u8; len];
+        rng.try_fill_bytes(&mut buf).err().map(|err| err.code().unwrap().get())
+    }
+
+    #[test]
+    fn test_cutoffs() {
+        // Values from SP 800-90B, table 2 (W = 512, alpha = 2^-20)
+        for &(h, cutoff) in &[(0.5, 410), (1.0, 311), (2.0, 177), (4.0, 62), (8.0, 13)] {
+            assert_eq!(critical_value(h, 20.0) + 1, cutoff);
+        }
+
+        assert_eq!(repetition_cutoff(8.0), 6);
+        assert_eq!(repetition_cutoff(1.0), 41);
+        assert!(proportion_cutoff(8.0) > 13);
+    }
+
+    #[test]
+    fn test_healthy() {
+        let mut rng = HealthTestRng::new(StepRng::new(0x0123_4567_89ab_cdef, 0x1111_1111_1111_1111), 8.0);
+        let mut expected = StepRng::new(0x0123_4567_89ab_cdef, 0x1111_1111_1111_1111);
+        for _ in 0..1000 {
+            assert_eq!(rng.next_u64(), expected.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_repetition_count() {
+        // stuck source
+        let mut rng = HealthTestRng::new(StepRng::new(0, 0), 8.0);
+        assert_eq!(failure_code(&mut rng, 5), None);
+        assert_eq!(failure_code(&mut rng, 1), Some(REPETITION_COUNT_FAILURE));
+        // the failure persists until reset
+        assert_eq!(failure_code(&mut rng, 1), Some(REPETITION_COUNT_FAILURE));
+        rng.reset();
+        assert_eq!(failure_code(&mut rng, 5), None);
+    }
+
+    #[test]
+    fn test_adaptive_proportion() {
+        // Little-endian bytes ff 00 00 00 ff 00 00 00 ...: no long runs, but
+        // the value of the first byte of each window is too frequent.
+        let mut rng = HealthTestRng::new(StepRng::new(0x0000_00ff_0000_00ff, 0), 8.0);
+        assert_eq!(failure_code(&mut rng, 512), Some(ADAPTIVE_PROPORTION_FAILURE));
+
+        let mut rng = HealthTestRng::with_cutoffs(StepRng::new(0x0000_00ff_0000_00ff, 0), 6, 129);
+        assert_eq!(failure_code(&mut rng, 4096), None);
+        let mut rng = HealthTestRng::with_cutoffs(StepRng::new(0x0000_00ff_0000_00ff, 0), 6, 128);
+        // the 128th occurrence is at index 508
+        assert_eq!(failure_code(&mut rng, 508), None);
+        assert_eq!(failure_code(&mut rng, 1), Some(ADAPTIVE_PROPORTION_FAILURE));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_bytes_panics() {
+        let mut rng = HealthTestRng::new(StepRng::new(0, 0), 8.0);
+        rng.next_u64();
+    }
+}
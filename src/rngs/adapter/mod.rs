@@ -8,8 +8,18 @@
 
 //! Wrappers / adapters forming RNGs
 
+#[cfg(all(feature = "std", feature = "async"))] mod async_read;
+#[cfg(feature = "std")] mod health;
 #[cfg(feature = "std")] mod read;
+#[cfg(feature = "std")] mod record;
 mod reseeding;
 
-#[cfg(feature = "std")] pub use self::read::{ReadError, ReadRng};
-pub use self::reseeding::ReseedingRng;
+#[cfg(all(feature = "std", feature = "async"))]
+pub use self::async_read::{AsyncRead, AsyncReadRng};
+#[cfg(feature = "std")]
+pub use self::health::{HealthTestRng, ADAPTIVE_PROPORTION_FAILURE, REPETITION_COUNT_FAILURE};
+#[cfg(feature = "std")] pub use self::read::{ReadError, ReadPolicy, ReadRng};
+#[cfg(feature = "std")]
+pub use self::record::{Call, RecordingRng, ReplayError, ReplayRng};
+pub use self::reseeding::{register_fork_handler, ReseedingRng};
+#[cfg(feature = "std")] pub use self::reseeding::notify_fork;
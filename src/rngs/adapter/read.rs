@@ -10,7 +10,7 @@
 //! A wrapper around any Read to treat it as an RNG.
 
 use std::fmt;
-use std::io::Read;
+use std::io::{self, Read};
 
 use rand_core::{impls, Error, RngCore};
 
@@ -23,12 +23,21 @@ use rand_core::{impls, Error, RngCore};
 /// This can be used with `/dev/urandom` on Unix but it is recommended to use
 /// [`OsRng`] instead.
 ///
-/// # Panics
+/// By default every request is passed on to the reader. A `ReadRng` created
+/// with [`ReadRng::with_capacity`] instead reads ahead in chunks of the given
+/// size, which reduces the number of reads (and thus system calls) for small
+/// requests; requests at least as large as the buffer bypass it.
 ///
-/// `ReadRng` uses [`std::io::Read::read_exact`], which retries on interrupts.
-/// All other errors from the underlying reader, including when it does not
-/// have enough data, will only be reported through [`try_fill_bytes`].
-/// The other [`RngCore`] methods will panic in case of an error.
+/// # Failure
+///
+/// Reads are retried on interrupts. What happens on any other error of the
+/// underlying reader, including when it does not have enough data, is
+/// determined by the [`ReadPolicy`], set with [`ReadRng::with_policy`]. By
+/// default ([`ReadPolicy::Error`]), errors are only reported through
+/// [`try_fill_bytes`], and the other [`RngCore`] methods panic. With
+/// [`ReadPolicy::Fallback`], bytes the reader could not provide are taken
+/// from another RNG instead, which makes the end of a recorded stream
+/// well-defined when replaying it in tests.
 ///
 /// # Example
 ///
@@ -43,15 +52,112 @@ use rand_core::{impls, Error, RngCore};
 ///
 /// [`OsRng`]: crate::rngs::OsRng
 /// [`try_fill_bytes`]: RngCore::try_fill_bytes
-#[derive(Debug)]
 pub struct ReadRng<R> {
     reader: R,
+    // Read-ahead buffer; empty when unbuffered. Bytes `pos..end` are unused.
+    buf: Vec<u8>,
+    pos: usize,
+    end: usize,
+    policy: ReadPolicy,
+}
+
+// Custom Debug implementation that does not expose the read-ahead bytes.
+impl<R: fmt::Debug> fmt::Debug for ReadRng<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadRng")
+            .field("reader", &self.reader)
+            .field("buf_len", &self.buf.len())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+/// What a [`ReadRng`] does when reading fails.
+pub enum ReadPolicy {
+    /// Panic in all [`RngCore`] methods, including `try_fill_bytes`.
+    Panic,
+    /// Report a [`ReadError`] through `try_fill_bytes`; the other
+    /// [`RngCore`] methods panic. This is the default.
+    Error,
+    /// Fill the bytes which could not be read from the given RNG, without
+    /// reporting the error. Errors of the fallback RNG are passed on.
+    Fallback(Box<dyn RngCore + Send>),
+}
+
+impl Default for ReadPolicy {
+    fn default() -> Self {
+        ReadPolicy::Error
+    }
+}
+
+impl fmt::Debug for ReadPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadPolicy::Panic => write!(f, "Panic"),
+            ReadPolicy::Error => write!(f, "Error"),
+            ReadPolicy::Fallback(_) => write!(f, "Fallback(..)"),
+        }
+    }
 }
 
 impl<R: Read> ReadRng<R> {
     /// Create a new `ReadRng` from a `Read`.
     pub fn new(r: R) -> ReadRng<R> {
-        ReadRng { reader: r }
+        ReadRng::with_capacity(r, 0)
+    }
+
+    /// Create a new `ReadRng` from a `Read`, reading ahead in chunks of
+    /// `capacity` bytes.
+    pub fn with_capacity(r: R, capacity: usize) -> ReadRng<R> {
+        ReadRng {
+            reader: r,
+            buf: vec![0; capacity],
+            pos: 0,
+            end: 0,
+            policy: ReadPolicy::default(),
+        }
+    }
+
+    /// Set the policy for read failures.
+    pub fn with_policy(mut self, policy: ReadPolicy) -> ReadRng<R> {
+        self.policy = policy;
+        self
+    }
+
+    /// Fill `dest`, counting the bytes filled in `filled`.
+    fn read_into(&mut self, dest: &mut [u8], filled: &mut usize) -> io::Result<()> {
+        while *filled < dest.len() {
+            let rest = &mut dest[*filled..];
+            if self.pos < self.end {
+                let n = rest.len().min(self.end - self.pos);
+                rest[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+                self.pos += n;
+                *filled += n;
+            } else if rest.len() >= self.buf.len() {
+                *filled += read_some(&mut self.reader, rest)?;
+            } else {
+                self.end = read_some(&mut self.reader, &mut self.buf)?;
+                self.pos = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read at least one byte, retrying on interrupts.
+fn read_some<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match reader.read(buf) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ))
+            }
+            Ok(n) => return Ok(n),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
     }
 }
 
@@ -74,19 +180,25 @@ impl<R: Read> RngCore for ReadRng<R> {
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        if dest.is_empty() {
-            return Ok(());
+        let mut filled = 0;
+        let err = match self.read_into(dest, &mut filled) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        match self.policy {
+            ReadPolicy::Panic => panic!(
+                "reading random bytes from Read implementation failed; error: {}",
+                err
+            ),
+            ReadPolicy::Error => Err(Error::new(ReadError(err))),
+            ReadPolicy::Fallback(ref mut rng) => rng.try_fill_bytes(&mut dest[filled..]),
         }
-        // Use `std::io::read_exact`, which retries on `ErrorKind::Interrupted`.
-        self.reader
-            .read_exact(dest)
-            .map_err(|e| Error::new(ReadError(e)))
     }
 }
 
 /// `ReadRng` error type
 #[derive(Debug)]
-pub struct ReadError(std::io::Error);
+pub struct ReadError(pub(crate) std::io::Error);
 
 impl fmt::Display for ReadError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -103,21 +215,36 @@ impl std::error::Error for ReadError {
 
 #[cfg(test)]
 mod test {
-    use super::ReadRng;
+    use std::io::{self, Read};
+
+    use super::{ReadPolicy, ReadRng};
+    use crate::rngs::mock::StepRng;
     use crate::RngCore;
 
+    /// A reader counting the calls to `read`.
+    struct CountingReader<'a> {
+        data: &'a [u8],
+        reads: usize,
+    }
+
+    impl<'a> Read for CountingReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads += 1;
+            self.data.read(buf)
+        }
+    }
+
     #[test]
     fn test_reader_rng_u64() {
-        // transmute from the target to avoid endianness concerns.
         #[rustfmt::skip]
         let v = vec![0u8, 0, 0, 0, 0, 0, 0, 1,
                      0  , 0, 0, 0, 0, 0, 0, 2,
                      0,   0, 0, 0, 0, 0, 0, 3];
         let mut rng = ReadRng::new(&v[..]);
 
-        assert_eq!(rng.next_u64(), 1_u64.to_be());
-        assert_eq!(rng.next_u64(), 2_u64.to_be());
-        assert_eq!(rng.next_u64(), 3_u64.to_be());
+        assert_eq!(rng.next_u64(), u64::from_le_bytes([0, 0, 0, 0, 0, 0, 0, 1]));
+        assert_eq!(rng.next_u64(), u64::from_le_bytes([0, 0, 0, 0, 0, 0, 0, 2]));
+        assert_eq!(rng.next_u64(), u64::from_le_bytes([0, 0, 0, 0, 0, 0, 0, 3]));
     }
 
     #[test]
@@ -125,9 +252,9 @@ mod test {
         let v = vec![0u8, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
         let mut rng = ReadRng::new(&v[..]);
 
-        assert_eq!(rng.next_u32(), 1_u32.to_be());
-        assert_eq!(rng.next_u32(), 2_u32.to_be());
-        assert_eq!(rng.next_u32(), 3_u32.to_be());
+        assert_eq!(rng.next_u32(), u32::from_le_bytes([0, 0, 0, 1]));
+        assert_eq!(rng.next_u32(), u32::from_le_bytes([0, 0, 0, 2]));
+        assert_eq!(rng.next_u32(), u32::from_le_bytes([0, 0, 0, 3]));
     }
 
     #[test]
@@ -141,6 +268,17 @@ mod test {
         assert!(v == w);
     }
 
+    #[test]
+    fn test_reader_rng_debug() {
+        let mut rng = ReadRng::with_capacity(io::repeat(0xab), 16);
+        rng.next_u32();
+
+        // The 12 bytes left in the buffer are not shown.
+        let debug = format!("{:?}", rng);
+        assert!(debug.contains("buf_len: 16"));
+        assert!(!debug.contains("171"));
+    }
+
     #[test]
     fn test_reader_rng_insufficient_bytes() {
         let v = [1u8, 2, 3, 4, 5, 6, 7, 8];
@@ -152,4 +290,54 @@ mod test {
         assert!(result.is_err());
         println!("Error: {}", result.unwrap_err());
     }
+
+    #[test]
+    fn test_reader_rng_buffered() {
+        let v: Vec<u8> = (0..100).collect();
+        let reader = CountingReader { data: &v[..], reads: 0 };
+        let mut rng = ReadRng::with_capacity(reader, 32);
+
+        let mut w = [0u8; 4];
+        for i in 0..8 {
+            rng.fill_bytes(&mut w);
+            assert_eq!(w[0], 4 * i);
+        }
+        assert_eq!(rng.reader.reads, 1);
+
+        // large requests bypass the buffer
+        let mut w = [0u8; 40];
+        rng.fill_bytes(&mut w);
+        assert_eq!(w[0], 32);
+        assert_eq!(rng.reader.reads, 2);
+
+        let mut w = [0u8; 28];
+        rng.fill_bytes(&mut w);
+        assert_eq!(w[27], 99);
+        assert!(rng.try_fill_bytes(&mut [0]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reader_rng_panic_policy() {
+        let v = [1u8, 2, 3, 4];
+        let mut rng = ReadRng::new(&v[..]).with_policy(ReadPolicy::Panic);
+        let _ = rng.try_fill_bytes(&mut [0u8; 5]);
+    }
+
+    #[test]
+    fn test_reader_rng_fallback_policy() {
+        let v = [1u8, 2, 3, 4, 5, 6];
+        let fallback = Box::new(StepRng::new(0xff, 0));
+        for &capacity in &[0, 4] {
+            let mut rng = ReadRng::with_capacity(&v[..], capacity)
+                .with_policy(ReadPolicy::Fallback(fallback.clone()));
+            let mut w = [0u8; 4];
+            rng.fill_bytes(&mut w);
+            assert_eq!(w, [1, 2, 3, 4]);
+            // the end of the data is followed by output of the fallback
+            rng.fill_bytes(&mut w);
+            assert_eq!(w, [5, 6, 0xff, 0]);
+            assert_eq!(rng.next_u32(), 0xff);
+        }
+    }
 }
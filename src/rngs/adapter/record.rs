@@ -0,0 +1,462 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Wrappers to record the output of an RNG and replay it later.
+//!
+//! A recording starts with the 5-byte header `RRNG\x01`, followed by one
+//! record per call, each starting with a tag byte:
+//!
+//! - `0x01`: `next_u32`, followed by the value in 4 little-endian bytes
+//! - `0x02`: `next_u64`, followed by the value in 8 little-endian bytes
+//! - `0x03`: `fill_bytes` or successful `try_fill_bytes`, followed by the
+//!   length as an unsigned LEB128 integer and the bytes
+//! - `0x04`: failed `try_fill_bytes`, followed by the length as an unsigned
+//!   LEB128 integer and the error code in 4 little-endian bytes (0 if the
+//!   error has no code)
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::num::NonZeroU32;
+
+use rand_core::{Error, RngCore};
+
+const MAGIC: &[u8; 5] = b"RRNG\x01";
+
+const TAG_U32: u8 = 0x01;
+const TAG_U64: u8 = 0x02;
+const TAG_BYTES: u8 = 0x03;
+const TAG_ERROR: u8 = 0x04;
+
+/// An RNG wrapper recording all output of the wrapped RNG, for replay with
+/// [`ReplayRng`].
+///
+/// Every call is recorded with its kind (`next_u32`, `next_u64`, or
+/// `fill_bytes` and `try_fill_bytes` with the length of the request), its
+/// output, and the error code if `try_fill_bytes` fails. The format is
+/// documented in the source of this module; a `BufWriter` should normally
+/// be used to write it to a file.
+///
+/// Recording does not change the output of the wrapped RNG. If writing
+/// fails, recording stops and the error is reported by
+/// [`RecordingRng::finish`].
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::adapter::{RecordingRng, ReplayRng};
+/// use rand::rngs::mock::StepRng;
+///
+/// let mut rng = RecordingRng::new(StepRng::new(1, 1), Vec::new()).unwrap();
+/// let x: u32 = rng.gen();
+/// let (_, recording) = rng.finish().unwrap();
+///
+/// let mut replay = ReplayRng::new(&recording[..]).unwrap();
+/// assert_eq!(replay.gen::<u32>(), x);
+/// ```
+#[derive(Debug)]
+pub struct RecordingRng<R, W: Write> {
+    inner: R,
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<R: RngCore, W: Write> RecordingRng<R, W> {
+    /// Create a new `RecordingRng`, writing the header of the recording.
+    pub fn new(inner: R, mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        Ok(RecordingRng {
+            inner,
+            writer,
+            error: None,
+        })
+    }
+
+    /// Flush the recording, and return the wrapped RNG and the writer.
+    ///
+    /// Returns the first error writing the recording, if any.
+    pub fn finish(mut self) -> io::Result<(R, W)> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.writer.flush()?;
+        Ok((self.inner, self.writer))
+    }
+
+    fn record(&mut self, tag: u8, len: Option<usize>, data: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut header = [tag; 11];
+        let mut n = 1;
+        if let Some(mut len) = len {
+            // unsigned LEB128
+            loop {
+                let byte = (len & 0x7f) as u8;
+                len >>= 7;
+                if len == 0 {
+                    header[n] = byte;
+                    n += 1;
+                    break;
+                }
+                header[n] = byte | 0x80;
+                n += 1;
+            }
+        }
+        let result = self
+            .writer
+            .write_all(&header[..n])
+            .and_then(|()| self.writer.write_all(data));
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl<R: RngCore, W: Write> RngCore for RecordingRng<R, W> {
+    fn next_u32(&mut self) -> u32 {
+        let value = self.inner.next_u32();
+        self.record(TAG_U32, None, &value.to_le_bytes());
+        value
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.inner.next_u64();
+        self.record(TAG_U64, None, &value.to_le_bytes());
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest);
+        self.record(TAG_BYTES, Some(dest.len()), dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match self.inner.try_fill_bytes(dest) {
+            Ok(()) => {
+                self.record(TAG_BYTES, Some(dest.len()), dest);
+                Ok(())
+            }
+            Err(err) => {
+                let code = err.code().map_or(0, NonZeroU32::get);
+                self.record(TAG_ERROR, Some(dest.len()), &code.to_le_bytes());
+                Err(err)
+            }
+        }
+    }
+}
+
+/// A kind of [`RngCore`] call, as recorded by [`RecordingRng`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    /// `next_u32`
+    NextU32,
+    /// `next_u64`
+    NextU64,
+    /// `fill_bytes` or `try_fill_bytes`, with the length of the request
+    FillBytes(usize),
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Call::NextU32 => write!(f, "next_u32"),
+            Call::NextU64 => write!(f, "next_u64"),
+            Call::FillBytes(len) => write!(f, "fill_bytes of {} bytes", len),
+        }
+    }
+}
+
+/// Error reported by [`ReplayRng`]. Calls are numbered from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The call differs from the recorded call.
+    Diverged {
+        /// The number of the call.
+        call: u64,
+        /// The recorded call.
+        recorded: Call,
+        /// The requested call.
+        requested: Call,
+    },
+    /// The recording ended before the call.
+    End {
+        /// The number of the call.
+        call: u64,
+    },
+    /// The record for the call could not be read, or is invalid.
+    Invalid {
+        /// The number of the call.
+        call: u64,
+        /// The kind of error reading the recording.
+        kind: io::ErrorKind,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::Diverged { call, recorded, requested } => write!(
+                f,
+                "replay diverged at call {}: recorded {}, requested {}",
+                call, recorded, requested
+            ),
+            ReplayError::End { call } => write!(f, "recording ended before call {}", call),
+            ReplayError::Invalid { call, kind } => {
+                write!(f, "invalid recording at call {}: {:?}", call, kind)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// An RNG replaying the output recorded by [`RecordingRng`].
+///
+/// Each call must be of the same kind as the recorded call, and, for
+/// `fill_bytes` and `try_fill_bytes`, request the same number of bytes.
+/// Otherwise replay has diverged, and instead of serving data meant for
+/// another call, `try_fill_bytes` reports a [`ReplayError`] identifying the
+/// first mismatching call; the other [`RngCore`] methods panic with the same
+/// information. The same happens when the recording ends. After an error,
+/// every further call fails with the same error.
+///
+/// Recorded errors of `try_fill_bytes` are replayed with the same error code;
+/// errors without code are replayed as an error with a message.
+#[derive(Debug)]
+pub struct ReplayRng<R> {
+    reader: R,
+    call: u64,
+    error: Option<ReplayError>,
+}
+
+impl<R: Read> ReplayRng<R> {
+    /// Create a new `ReplayRng`, reading and checking the header of the
+    /// recording.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 5];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not an RNG recording"));
+        }
+        Ok(ReplayRng {
+            reader,
+            call: 0,
+            error: None,
+        })
+    }
+
+    /// The number of calls replayed so far.
+    pub fn calls(&self) -> u64 {
+        self.call
+    }
+
+    /// Replay the next call, writing its output to `dest` (which must have
+    /// the length of the output).
+    fn replay(&mut self, requested: Call, dest: &mut [u8]) -> Result<(), Error> {
+        if let Some(ref err) = self.error {
+            return Err(Error::new(err.clone()));
+        }
+        let result = self.read_record(requested, dest);
+        if let Err(ref err) = result {
+            self.error = Some(err.clone());
+        }
+        self.call += 1;
+        match result {
+            Ok(None) => Ok(()),
+            Ok(Some(code)) => Err(match NonZeroU32::new(code) {
+                Some(code) => Error::from(code),
+                None => Error::new("replayed error without code"),
+            }),
+            Err(err) => Err(Error::new(err)),
+        }
+    }
+
+    /// Read the next record, returning the recorded error code, if any.
+    fn read_record(&mut self, requested: Call, dest: &mut [u8]) -> Result<Option<u32>, ReplayError> {
+        let call = self.call;
+        let invalid = |err: io::Error| ReplayError::Invalid { call, kind: err.kind() };
+
+        let mut tag = [0u8];
+        if self.reader.read(&mut tag).map_err(invalid)? == 0 {
+            return Err(ReplayError::End { call });
+        }
+        let recorded = match tag[0] {
+            TAG_U32 => Call::NextU32,
+            TAG_U64 => Call::NextU64,
+            TAG_BYTES | TAG_ERROR => Call::FillBytes(self.read_len().map_err(invalid)?),
+            _ => return Err(invalid(io::ErrorKind::InvalidData.into())),
+        };
+        if recorded != requested {
+            return Err(ReplayError::Diverged { call, recorded, requested });
+        }
+        if tag[0] == TAG_ERROR {
+            let mut code = [0u8; 4];
+            self.reader.read_exact(&mut code).map_err(invalid)?;
+            return Ok(Some(u32::from_le_bytes(code)));
+        }
+        self.reader.read_exact(dest).map_err(invalid)?;
+        Ok(None)
+    }
+
+    fn read_len(&mut self) -> io::Result<usize> {
+        let mut len = 0usize;
+        let mut shift = 0;
+        loop {
+            let mut byte = [0u8];
+            self.reader.read_exact(&mut byte)?;
+            // Reject lengths which do not fit in `usize`.
+            let bits = usize::from(byte[0] & 0x7f);
+            len |= match bits.checked_shl(shift) {
+                Some(shifted) if shifted >> shift == bits => shifted,
+                _ => return Err(io::ErrorKind::InvalidData.into()),
+            };
+            if byte[0] & 0x80 == 0 {
+                return Ok(len);
+            }
+            shift += 7;
+        }
+    }
+}
+
+impl<R: Read> RngCore for ReplayRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.replay(Call::NextU32, &mut bytes)
+            .unwrap_or_else(|err| panic!("ReplayRng: {}", err));
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.replay(Call::NextU64, &mut bytes)
+            .unwrap_or_else(|err| panic!("ReplayRng: {}", err));
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .unwrap_or_else(|err| panic!("ReplayRng: {}", err));
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.replay(Call::FillBytes(dest.len()), dest)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::{Call, RecordingRng, ReplayError, ReplayRng, MAGIC, TAG_BYTES};
+    use crate::rngs::mock::StepRng;
+    use crate::RngCore;
+
+    fn record() -> (Vec<u8>, u32, u64, [u8; 200]) {
+        let mut rng = RecordingRng::new(StepRng::new(0x0123_4567_89ab_cdef, 3), Vec::new()).unwrap();
+        let x = rng.next_u32();
+        let y = rng.next_u64();
+        let mut bytes = [0u8; 200];
+        rng.try_fill_bytes(&mut bytes).unwrap();
+        let (_, recording) = rng.finish().unwrap();
+        (recording, x, y, bytes)
+    }
+
+    fn replay_error(rng: &mut ReplayRng<&[u8]>, dest: &mut [u8]) -> ReplayError {
+        let err = rng.try_fill_bytes(dest).unwrap_err();
+        err.take_inner().downcast::<ReplayError>().map(|err| *err).unwrap()
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let (recording, x, y, bytes) = record();
+        // header, 2 + 4, 2 + 8, 1 + 2 + 200
+        assert_eq!(recording.len(), 5 + 5 + 9 + 203);
+
+        let mut rng = ReplayRng::new(&recording[..]).unwrap();
+        assert_eq!(rng.next_u32(), x);
+        assert_eq!(rng.next_u64(), y);
+        let mut replayed = [0u8; 200];
+        rng.fill_bytes(&mut replayed);
+        assert_eq!(replayed[..], bytes[..]);
+        assert_eq!(rng.calls(), 3);
+        assert_eq!(replay_error(&mut rng, &mut [0; 4]), ReplayError::End { call: 3 });
+    }
+
+    #[test]
+    fn test_replay_divergence() {
+        let (recording, x, _, _) = record();
+        let mut rng = ReplayRng::new(&recording[..]).unwrap();
+        assert_eq!(rng.next_u32(), x);
+        let expected = ReplayError::Diverged {
+            call: 1,
+            recorded: Call::NextU64,
+            requested: Call::FillBytes(8),
+        };
+        assert_eq!(replay_error(&mut rng, &mut [0; 8]), expected);
+        // the error persists
+        assert_eq!(replay_error(&mut rng, &mut [0; 8]), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "replay diverged at call 2: recorded fill_bytes of 200 bytes, \
+                               requested fill_bytes of 100 bytes")]
+    fn test_replay_divergence_panics() {
+        let (recording, _, _, _) = record();
+        let mut rng = ReplayRng::new(&recording[..]).unwrap();
+        rng.next_u32();
+        rng.next_u64();
+        rng.fill_bytes(&mut [0; 100]);
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        use crate::test::FailingRng;
+
+        let mut rng = RecordingRng::new(FailingRng, Vec::new()).unwrap();
+        assert!(rng.try_fill_bytes(&mut [0; 300]).is_err());
+        let (_, recording) = rng.finish().unwrap();
+
+        let mut rng = ReplayRng::new(&recording[..]).unwrap();
+        let err = rng.try_fill_bytes(&mut [0; 300]).unwrap_err();
+        assert_eq!(err.code().unwrap().get(), FailingRng::CODE);
+
+        // truncated recording
+        let mut rng = ReplayRng::new(&recording[..recording.len() - 1]).unwrap();
+        let err = replay_error(&mut rng, &mut [0; 300]);
+        assert_eq!(err, ReplayError::Invalid {
+            call: 0,
+            kind: std::io::ErrorKind::UnexpectedEof,
+        });
+
+        assert!(ReplayRng::new(&b"RNG"[..]).is_err());
+    }
+
+    #[test]
+    fn test_replay_invalid_length() {
+        let replay = |len: &[u8]| {
+            let mut recording = MAGIC.to_vec();
+            recording.push(TAG_BYTES);
+            recording.extend_from_slice(len);
+            let mut rng = ReplayRng::new(&recording[..]).unwrap();
+            replay_error(&mut rng, &mut [0; 4])
+        };
+
+        // truncated length
+        assert_eq!(replay(&[0x84, 0x80]), ReplayError::Invalid {
+            call: 0,
+            kind: std::io::ErrorKind::UnexpectedEof,
+        });
+        // 2^70 - 1, which does not fit in `usize`
+        let mut len = [0xff; 10];
+        len[9] = 0x7f;
+        assert_eq!(replay(&len), ReplayError::Invalid {
+            call: 0,
+            kind: std::io::ErrorKind::InvalidData,
+        });
+    }
+}
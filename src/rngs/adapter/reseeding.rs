@@ -14,6 +14,7 @@ use core::mem::size_of;
 
 use rand_core::block::{BlockRng, BlockRngCore};
 use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
 
 /// A wrapper around any PRNG that implements [`BlockRngCore`], that adds the
 /// ability to reseed it.
@@ -22,10 +23,10 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 ///
 /// - On a manual call to [`reseed()`].
 /// - After `clone()`, the clone will be reseeded on first use.
-/// - After a process is forked, the RNG in the child process is reseeded within
-///   the next few generated values, depending on the block size of the
-///   underlying PRNG. For ChaCha and Hc128 this is a maximum of
-///   15 `u32` values before reseeding.
+/// - After a process is forked, the RNG in the child process is reseeded before
+///   it generates the next value; buffered values are discarded. Forks are
+///   detected with `pthread_atfork` on Unix; runtimes forking by other means
+///   can call [`notify_fork`] in the child.
 /// - After the PRNG has generated a configurable number of random bytes.
 ///
 /// # When should reseeding after a fixed number of generated bytes be used?
@@ -54,6 +55,15 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// Manually calling [`reseed()`] will not have this retry or delay logic, but
 /// reports the error.
 ///
+/// # Serialization
+///
+/// With the `serde1` feature, `ReseedingRng` can be serialized if both the
+/// wrapped PRNG and the reseeder can. The snapshot includes the buffered
+/// output and the number of bytes remaining until the next reseed, so a
+/// restored RNG produces the same values as the original until that point.
+/// A restored RNG is not considered forked, even if it was serialized in a
+/// different process.
+///
 /// # Example
 ///
 /// ```
@@ -75,7 +85,16 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// [`BlockRngCore`]: rand_core::block::BlockRngCore
 /// [`ReseedingRng::new`]: ReseedingRng::new
 /// [`reseed()`]: ReseedingRng::reseed
+/// [`notify_fork`]: crate::rngs::adapter::notify_fork
 #[derive(Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde1",
+    serde(bound(
+        serialize = "R: Serialize, R::Results: Serialize, Rsdr: Serialize",
+        deserialize = "R: Deserialize<'de>, R::Results: Deserialize<'de>, Rsdr: Deserialize<'de>"
+    ))
+)]
 pub struct ReseedingRng<R, Rsdr>(BlockRng<ReseedingCore<R, Rsdr>>)
 where
     R: BlockRngCore + SeedableRng,
@@ -100,6 +119,15 @@ where
     pub fn reseed(&mut self) -> Result<(), Error> {
         self.0.core.reseed()
     }
+
+    /// Discard the buffered values if the process was forked since the last
+    /// reseed, such that the next value is generated after reseeding.
+    #[inline(always)]
+    fn check_fork(&mut self) {
+        if self.0.core.is_forked(fork::get_fork_counter()) {
+            self.0.reset();
+        }
+    }
 }
 
 // TODO: this should be implemented for any type where the inner type
@@ -111,19 +139,23 @@ where
 {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
+        self.check_fork();
         self.0.next_u32()
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
+        self.check_fork();
         self.0.next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.check_fork();
         self.0.fill_bytes(dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.check_fork();
         self.0.try_fill_bytes(dest)
     }
 }
@@ -148,14 +180,25 @@ where
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 struct ReseedingCore<R, Rsdr> {
     inner: R,
     reseeder: Rsdr,
     threshold: i64,
     bytes_until_reseed: i64,
+    // The fork counter is only meaningful within a single process, so it is
+    // not serialized. A restored RNG is synchronized with the current process
+    // instead, such that it does not immediately reseed.
+    #[cfg_attr(feature = "serde1", serde(skip, default = "current_fork_counter"))]
     fork_counter: usize,
 }
 
+#[cfg(feature = "serde1")]
+fn current_fork_counter() -> usize {
+    fork::register_fork_handler();
+    fork::get_fork_counter()
+}
+
 impl<R, Rsdr> BlockRngCore for ReseedingCore<R, Rsdr>
 where
     R: BlockRngCore + SeedableRng,
@@ -279,10 +322,36 @@ where
 }
 
 
-#[cfg(all(unix, feature = "std", not(target_os = "emscripten")))]
+/// Register the fork handler used by [`ReseedingRng`] to detect forks.
+///
+/// On Unix (with the `std` feature) this registers a handler with
+/// `pthread_atfork`, such that every `ReseedingRng` in the child of a `fork`
+/// reseeds itself before generating the next value. The handler is registered
+/// only once, and automatically when a `ReseedingRng` is created; calling this
+/// function is only useful to ensure registration happens before the process
+/// forks for the first time, e.g. when `ReseedingRng`s may be created
+/// concurrently with forking. On other platforms this is a no-op.
+///
+/// Forks which bypass libc's `fork` (e.g. a raw `clone` system call) do not
+/// run `pthread_atfork` handlers; use [`notify_fork`] in the child instead.
+pub fn register_fork_handler() {
+    fork::register_fork_handler()
+}
+
+/// Notify all [`ReseedingRng`]s in this process that the process was forked.
+///
+/// Every `ReseedingRng` reseeds itself before generating its next value. This
+/// is what the handler installed by [`register_fork_handler`] does, and is
+/// meant to be called in the child by runtimes which fork without running
+/// `pthread_atfork` handlers. It is async-signal-safe.
+#[cfg(feature = "std")]
+pub fn notify_fork() {
+    fork::notify_fork()
+}
+
+#[cfg(feature = "std")]
 mod fork {
     use core::sync::atomic::{AtomicUsize, Ordering};
-    use std::sync::Once;
 
     // Fork protection
     //
@@ -302,21 +371,31 @@ mod fork {
         RESEEDING_RNG_FORK_COUNTER.load(Ordering::Relaxed)
     }
 
-    extern "C" fn fork_handler() {
+    pub fn notify_fork() {
         // Note: fetch_add is defined to wrap on overflow
         // (which is what we want).
         RESEEDING_RNG_FORK_COUNTER.fetch_add(1, Ordering::Relaxed);
     }
 
+    #[cfg(all(unix, not(target_os = "emscripten")))]
     pub fn register_fork_handler() {
+        use std::sync::Once;
+
+        extern "C" fn fork_handler() {
+            notify_fork();
+        }
+
         static REGISTER: Once = Once::new();
         REGISTER.call_once(|| unsafe {
             libc::pthread_atfork(None, None, Some(fork_handler));
         });
     }
+
+    #[cfg(not(all(unix, not(target_os = "emscripten"))))]
+    pub fn register_fork_handler() {}
 }
 
-#[cfg(not(all(unix, feature = "std", not(target_os = "emscripten"))))]
+#[cfg(not(feature = "std"))]
 mod fork {
     pub fn get_fork_counter() -> usize {
         0
@@ -353,6 +432,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_reseeding_drbg() {
+        use rand_drbg::{DrbgSeed, HmacDrbgCore};
+
+        let mut zero = StepRng::new(0, 0);
+        let core = HmacDrbgCore::from_seed(DrbgSeed::default());
+        // reseed every time the buffer of [u32; 32] is exhausted
+        let mut reseeding = ReseedingRng::new(core, 1, &mut zero);
+        let mut buf = [0u32; 32];
+        reseeding.fill(&mut buf);
+        let seq = buf;
+        for _ in 0..10 {
+            reseeding.fill(&mut buf);
+            assert_eq!(buf, seq);
+        }
+    }
+
     #[test]
     fn test_clone_reseeding() {
         let mut zero = StepRng::new(0, 0);
@@ -367,4 +463,61 @@ mod test {
         let mut rng2 = rng1.clone();
         assert_eq!(first, rng2.gen::<u32>());
     }
+
+    // `Core` is that of `StdRng`
+    #[cfg(feature = "serde1_std_rng")]
+    #[test]
+    fn test_reseeding_serde() {
+        let mut step = StepRng::new(0, 1);
+        let rng = Core::from_rng(&mut step).unwrap();
+        // reseed every second block of 64 words
+        let mut rng1 = ReseedingRng::new(rng, 2 * 64 * 4, step);
+        for _ in 0..100 {
+            let _ = rng1.gen::<u32>();
+        }
+
+        let encoded = bincode::serialize(&rng1).expect("Could not serialize");
+        let mut rng2: ReseedingRng<Core, StepRng> =
+            bincode::deserialize(&encoded).expect("Could not deserialize");
+        // Continue across several reseeds; both reseeders are in the same state,
+        // so the outputs only match if the reseed counter was restored too.
+        for _ in 0..1000 {
+            assert_eq!(rng1.gen::<u32>(), rng2.gen::<u32>());
+        }
+    }
+
+    #[cfg(all(unix, feature = "std", not(target_os = "emscripten")))]
+    #[test]
+    fn test_fork() {
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut rng = ReseedingRng::new(rng, 0, StepRng::new(1, 1));
+        // buffer a block, which must not be used after the fork
+        let _ = rng.gen::<u32>();
+
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0);
+        if pid == 0 {
+            // Child: only use async-signal-safe functions.
+            let x: [u32; 4] = rng.gen();
+            unsafe {
+                libc::write(fds[1], x.as_ptr() as *const libc::c_void, 16);
+                libc::_exit(0);
+            }
+        }
+
+        let parent: [u32; 4] = rng.gen();
+        let mut child = [0u32; 4];
+        let n = unsafe { libc::read(fds[0], child.as_mut_ptr() as *mut libc::c_void, 16) };
+        let mut status = 0;
+        unsafe {
+            libc::waitpid(pid, &mut status, 0);
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+        assert_eq!(n, 16);
+        assert_ne!(parent, child);
+    }
 }
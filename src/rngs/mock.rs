@@ -9,6 +9,7 @@
 //! Mock random number generator
 
 use rand_core::{impls, Error, RngCore};
+#[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
 
 /// A simple implementation of `RngCore` for testing purposes.
 ///
@@ -25,6 +26,7 @@ use rand_core::{impls, Error, RngCore};
 /// assert_eq!(sample, [2, 3, 4]);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct StepRng {
     v: u64,
     a: u64,
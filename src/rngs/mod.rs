@@ -102,7 +102,9 @@ pub mod mock; // Public so we don't export `StepRng` directly, making it a bit
               // more clear it is intended for testing.
 #[cfg(feature = "small_rng")] mod small;
 #[cfg(feature = "std_rng")] mod std;
-#[cfg(all(feature = "std", feature = "std_rng"))] pub(crate) mod thread;
+#[cfg(all(feature = "std", feature = "std_rng", not(feature = "deterministic_thread_rng")))]
+pub(crate) mod thread;
+#[cfg(feature = "deterministic_thread_rng")] pub mod thread;
 
 #[cfg(feature = "small_rng")] pub use self::small::SmallRng;
 #[cfg(feature = "std_rng")] pub use self::std::StdRng;
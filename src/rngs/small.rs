@@ -9,6 +9,7 @@
 //! A small fast RNG
 
 use rand_core::{Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1_small_rng")] use serde::{Deserialize, Serialize};
 
 #[cfg(all(not(target_os = "emscripten"), target_pointer_width = "64"))]
 type Rng = rand_pcg::Pcg64Mcg;
@@ -69,11 +70,17 @@ type Rng = rand_pcg::Pcg32;
 ///     .collect();
 /// ```
 ///
+/// With the `serde1_small_rng` feature (`serde1` combined with `small_rng`),
+/// the state of a `SmallRng` may be serialized and restored later. Since the
+/// algorithm depends on the platform, a snapshot should only be restored on
+/// the same platform and version of Rand.
+///
 /// [`StdRng`]: crate::rngs::StdRng
 /// [`thread_rng`]: crate::thread_rng
 /// [rand_chacha]: https://crates.io/crates/rand_chacha
 /// [rand_pcg]: https://crates.io/crates/rand_pcg
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1_small_rng", derive(Serialize, Deserialize))]
 pub struct SmallRng(Rng);
 
 impl RngCore for SmallRng {
@@ -111,3 +118,23 @@ impl SeedableRng for SmallRng {
         Rng::from_rng(rng).map(SmallRng)
     }
 }
+
+#[cfg(all(test, feature = "serde1_small_rng"))]
+mod test {
+    use crate::rngs::SmallRng;
+    use crate::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_smallrng_serde() {
+        let mut rng = SmallRng::seed_from_u64(0x0123_4567);
+        for _ in 0..37 {
+            rng.next_u32();
+        }
+
+        let encoded = bincode::serialize(&rng).expect("Could not serialize");
+        let mut decoded: SmallRng = bincode::deserialize(&encoded).expect("Could not deserialize");
+        for _ in 0..200 {
+            assert_eq!(rng.next_u64(), decoded.next_u64());
+        }
+    }
+}
@@ -9,6 +9,7 @@
 //! The standard RNG
 
 use crate::{CryptoRng, Error, RngCore, SeedableRng};
+#[cfg(feature = "serde1_std_rng")] use serde::{Deserialize, Serialize};
 
 #[cfg(all(any(test, feature = "std"), not(target_os = "emscripten")))]
 pub(crate) use rand_chacha::ChaCha20Core as Core;
@@ -31,8 +32,14 @@ pub(crate) use rand_hc::Hc128Core as Core;
 /// library versions. For a secure reproducible generator, we recommend use of
 /// the [rand_chacha] crate directly.
 ///
+/// With the `serde1_std_rng` feature (`serde1` combined with `std_rng`), the
+/// complete state of a `StdRng` may be serialized and restored later, e.g. to
+/// replay a failing test. As above, a snapshot should only be restored by the
+/// same version of Rand on the same platform.
+///
 /// [rand_chacha]: https://crates.io/crates/rand_chacha
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1_std_rng", derive(Serialize, Deserialize))]
 pub struct StdRng(Rng);
 
 impl RngCore for StdRng {
@@ -100,4 +107,19 @@ mod test {
 
         assert_eq!([x0, x1], target);
     }
+
+    #[cfg(feature = "serde1_std_rng")]
+    #[test]
+    fn test_stdrng_serde() {
+        let mut rng = StdRng::seed_from_u64(0x0123_4567);
+        for _ in 0..37 {
+            rng.next_u32();
+        }
+
+        let encoded = bincode::serialize(&rng).expect("Could not serialize");
+        let mut decoded: StdRng = bincode::deserialize(&encoded).expect("Could not deserialize");
+        for _ in 0..200 {
+            assert_eq!(rng.next_u64(), decoded.next_u64());
+        }
+    }
 }
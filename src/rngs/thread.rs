@@ -51,25 +51,175 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 /// attacks and mis-use (e.g. if somehow weak entropy were supplied initially).
 /// The PRNG algorithms used are assumed to be secure.
 ///
+/// With the `deterministic_thread_rng` feature, [`set_seed`] switches
+/// `ThreadRng` to a reproducible mode intended for testing.
+///
+/// `ThreadRng` cannot be serialized, even with the `serde1` feature: it is
+/// only a handle, and restoring the state of the shared generator would make
+/// the output of every user of `thread_rng` in that thread predictable. To
+/// replay a test, seed `thread_rng` with [`set_seed`] instead, or pass the
+/// test a [`StdRng`], which can be serialized.
+///
 /// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
 /// [`StdRng`]: crate::rngs::StdRng
+/// [`set_seed`]: crate::rngs::thread::set_seed
 #[derive(Copy, Clone, Debug)]
 pub struct ThreadRng {
     // inner raw pointer implies type is neither Send nor Sync
     opaque: PhantomData<*mut ()>
 }
 
+// The generator is created on first use rather than in the initializer of the
+// thread-local, such that `try_thread_rng` can report a failure to seed it.
 thread_local!(
-    static THREAD_RNG_KEY: UnsafeCell<ReseedingRng<Core, OsRng>> = {
-        let r = Core::from_rng(OsRng).unwrap_or_else(|err|
-                panic!("could not initialize thread_rng: {}", err));
-        let rng = ReseedingRng::new(r,
-                                    THREAD_RNG_RESEED_THRESHOLD,
-                                    OsRng);
-        UnsafeCell::new(rng)
-    }
+    static THREAD_RNG_KEY: UnsafeCell<Option<ReseedingRng<Core, OsRng>>> =
+        UnsafeCell::new(None)
 );
 
+/// Create the generator of the current thread.
+fn new_thread_rng() -> Result<ReseedingRng<Core, OsRng>, Error> {
+    #[cfg(feature = "deterministic_thread_rng")] {
+        if let Some(rng) = seeded::next_thread_rng() {
+            return Ok(rng);
+        }
+    }
+    seed_thread_rng(OsRng)
+}
+
+fn seed_thread_rng<S: RngCore>(seeder: S) -> Result<ReseedingRng<Core, OsRng>, Error> {
+    let r = Core::from_rng(seeder)?;
+    Ok(ReseedingRng::new(r, THREAD_RNG_RESEED_THRESHOLD, OsRng))
+}
+
+#[cold]
+#[inline(never)]
+fn init_thread_rng(slot: &mut Option<ReseedingRng<Core, OsRng>>) -> &mut ReseedingRng<Core, OsRng> {
+    let rng = new_thread_rng().unwrap_or_else(|err|
+            panic!("could not initialize thread_rng: {}", err));
+    slot.get_or_insert(rng)
+}
+
+/// Run `f` on the generator of the current thread, creating it if necessary.
+#[inline(always)]
+fn with_thread_rng<T, F>(f: F) -> T
+where F: FnOnce(&mut ReseedingRng<Core, OsRng>) -> T {
+    THREAD_RNG_KEY.with(|key| {
+        let slot = unsafe { &mut *key.get() };
+        match slot {
+            Some(rng) => f(rng),
+            None => f(init_thread_rng(slot)),
+        }
+    })
+}
+
+/// Make `ThreadRng` deterministic, for reproducible (multithreaded) tests.
+///
+/// After this call, the generator of each thread is derived from `seed` and
+/// an ordinal: the calling thread gets ordinal 0 and is reseeded immediately,
+/// other threads are numbered 1, 2, ... in the order in which they first use
+/// [`thread_rng`]. Threads which already used `thread_rng` before this call
+/// are unaffected. The output of a thread is thus reproducible as long as
+/// threads first use `thread_rng` in a reproducible order.
+///
+/// Seeded generators never reseed themselves, except in the child after a
+/// process fork, which is reseeded from [`OsRng`] as usual. Calling `set_seed`
+/// again resets the ordinals, hence the sequence restarts. Use [`clear_seed`]
+/// to leave this mode.
+///
+/// This is process-wide state; it should not be used outside of tests.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::thread::{clear_seed, set_seed};
+///
+/// set_seed(42);
+/// let x: u64 = rand::thread_rng().gen();
+/// set_seed(42);
+/// assert_eq!(x, rand::thread_rng().gen::<u64>());
+/// clear_seed();
+/// ```
+#[cfg(feature = "deterministic_thread_rng")]
+pub fn set_seed(seed: u64) {
+    let rng = seeded::set_master(seed);
+    THREAD_RNG_KEY.with(|key| unsafe { *key.get() = Some(rng) });
+}
+
+/// Leave the deterministic mode entered by [`set_seed`].
+///
+/// The generator of the calling thread is discarded, so that it is seeded
+/// from [`OsRng`] on its next use, as are threads which first use
+/// [`thread_rng`] after this call. Other threads keep their seeded generators.
+/// Does nothing if no seed is set.
+#[cfg(feature = "deterministic_thread_rng")]
+pub fn clear_seed() {
+    if seeded::clear_master() {
+        THREAD_RNG_KEY.with(|key| unsafe { *key.get() = None });
+    }
+}
+
+#[cfg(feature = "deterministic_thread_rng")]
+mod seeded {
+    use std::sync::{Mutex, Once};
+
+    use super::Core;
+    use crate::rngs::adapter::ReseedingRng;
+    use crate::rngs::OsRng;
+    use crate::SeedableRng;
+    use rand_core::{RngCore, SplitMix64};
+
+    struct State {
+        master: u64,
+        next_ordinal: u64,
+    }
+
+    // `Mutex::new` is not `const` on our minimum supported Rust version.
+    fn state() -> &'static Mutex<Option<State>> {
+        static INIT: Once = Once::new();
+        static mut STATE: *const Mutex<Option<State>> = std::ptr::null();
+        unsafe {
+            INIT.call_once(|| {
+                STATE = Box::into_raw(Box::new(Mutex::new(None)));
+            });
+            &*STATE
+        }
+    }
+
+    fn derive(master: u64, ordinal: u64) -> ReseedingRng<Core, OsRng> {
+        let mut seed = <Core as SeedableRng>::Seed::default();
+        SplitMix64::new(master).fill_bytes(seed.as_mut());
+        for (x, o) in seed.as_mut().iter_mut().zip(ordinal.to_le_bytes().iter()) {
+            *x ^= *o;
+        }
+        // A threshold of 0 disables reseeding.
+        ReseedingRng::new(Core::from_seed(seed), 0, OsRng)
+    }
+
+    /// Set the master seed and return the generator for ordinal 0.
+    pub(super) fn set_master(master: u64) -> ReseedingRng<Core, OsRng> {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        *state = Some(State { master, next_ordinal: 1 });
+        derive(master, 0)
+    }
+
+    /// Clear the master seed, returning whether one was set.
+    pub(super) fn clear_master() -> bool {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        state.take().is_some()
+    }
+
+    /// Return the generator for the next ordinal, if a master seed is set.
+    pub(super) fn next_thread_rng() -> Option<ReseedingRng<Core, OsRng>> {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        state.as_mut().map(|state| {
+            let ordinal = state.next_ordinal;
+            state.next_ordinal += 1;
+            derive(state.master, ordinal)
+        })
+    }
+}
+
 /// Retrieve the lazily-initialized thread-local random number generator,
 /// seeded by the system. Intended to be used in method chaining style,
 /// e.g. `thread_rng().gen::<i32>()`, or cached locally, e.g.
@@ -77,12 +227,46 @@ thread_local!(
 /// `ThreadRng::default()` equivalent.
 ///
 /// For more information see [`ThreadRng`].
+///
+/// # Panics
+///
+/// Using the returned generator panics if the thread-local generator cannot
+/// be seeded from [`OsRng`]. Use [`try_thread_rng`] to handle this case.
+///
+/// [`try_thread_rng`]: crate::try_thread_rng
 pub fn thread_rng() -> ThreadRng {
     ThreadRng {
         opaque: PhantomData
     }
 }
 
+/// Retrieve the thread-local random number generator, seeding it from
+/// [`OsRng`] if this was not done yet, without panicking.
+///
+/// Unlike [`thread_rng`], this reports failure to obtain entropy (e.g. in
+/// a sandbox without access to the system's entropy source) as an [`Error`],
+/// which keeps the error code reported by [`OsRng`]. Once this returned `Ok`,
+/// the generator of the current thread is initialized and using it (through
+/// any `ThreadRng` handle) does not panic.
+///
+/// ```
+/// use rand::Rng;
+///
+/// match rand::try_thread_rng() {
+///     Ok(mut rng) => println!("{}", rng.gen::<u32>()),
+///     Err(err) => eprintln!("no entropy: {}", err),
+/// }
+/// ```
+pub fn try_thread_rng() -> Result<ThreadRng, Error> {
+    THREAD_RNG_KEY.try_with(|key| {
+        let slot = unsafe { &mut *key.get() };
+        if slot.is_none() {
+            *slot = Some(new_thread_rng()?);
+        }
+        Ok(ThreadRng { opaque: PhantomData })
+    }).map_err(Error::new)?
+}
+
 impl Default for ThreadRng {
     fn default() -> ThreadRng {
         crate::prelude::thread_rng()
@@ -92,27 +276,26 @@ impl Default for ThreadRng {
 impl RngCore for ThreadRng {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        THREAD_RNG_KEY.with(|rng| {
-            unsafe { (*rng.get()).next_u32() }
-        })
+        with_thread_rng(|rng| rng.next_u32())
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        THREAD_RNG_KEY.with(|rng| {
-            unsafe { (*rng.get()).next_u64() }
-        })
+        with_thread_rng(|rng| rng.next_u64())
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        THREAD_RNG_KEY.with(|rng| {
-            unsafe { (*rng.get()).fill_bytes(dest) }
-        })
+        with_thread_rng(|rng| rng.fill_bytes(dest))
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        THREAD_RNG_KEY.try_with(|rng| {
-            unsafe { (*rng.get()).try_fill_bytes(dest) }
+        THREAD_RNG_KEY.try_with(|key| {
+            let slot = unsafe { &mut *key.get() };
+            let rng = match slot {
+                Some(rng) => rng,
+                None => slot.get_or_insert(new_thread_rng()?),
+            };
+            rng.try_fill_bytes(dest)
         }).map_err(|e| Error::new(e))?
     }
 }
@@ -130,6 +313,25 @@ mod test {
         assert_eq!(r.gen_range(0, 1), 0);
     }
 
+    #[test]
+    fn test_try_thread_rng() {
+        use crate::{Rng, RngCore};
+        let mut r = crate::try_thread_rng().unwrap();
+        r.gen::<i32>();
+        let mut buf = [0u8; 16];
+        r.try_fill_bytes(&mut buf).unwrap();
+    }
+
+    #[test]
+    fn test_thread_rng_failing_source() {
+        use crate::test::FailingRng;
+
+        match super::seed_thread_rng(FailingRng) {
+            Ok(_) => panic!("seeding from a failing source succeeded"),
+            Err(err) => assert_eq!(err.code().unwrap().get(), FailingRng::CODE),
+        }
+    }
+
     // Causes use-after-free on OSX. The following flags are needed to disable the "fast"
     // implementation on OSX and turn use-after-destroy into use-after-free.
     // CARGO_BUILD_RUSTFLAGS="-C link-arg=-mmacosx-version-min=10.14" MACOSX_DEPLOYMENT_TARGET=10.6 cargo test test_lifetime
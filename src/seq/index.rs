@@ -456,11 +456,11 @@ mod test {
             );
         };
 
-        do_test(10, 6, &[8, 0, 3, 5, 9, 6]); // floyd
-        do_test(25, 10, &[18, 15, 14, 9, 0, 13, 5, 24]); // floyd
-        do_test(300, 8, &[30, 283, 150, 1, 73, 13, 285, 35]); // floyd
-        do_test(300, 80, &[31, 289, 248, 154, 5, 78, 19, 286]); // inplace
-        do_test(300, 180, &[31, 289, 248, 154, 5, 78, 19, 286]); // inplace
+        do_test(10, 6, &[0, 9, 8, 6, 5, 4]); // floyd
+        do_test(25, 10, &[24, 1, 20, 16, 19, 22, 14, 9]); // floyd
+        do_test(300, 8, &[30, 283, 243, 150, 218, 240, 1, 189]); // floyd
+        do_test(300, 80, &[31, 289, 248, 154, 221, 243, 7, 192]); // inplace
+        do_test(300, 180, &[31, 289, 248, 154, 221, 243, 7, 192]); // inplace
 
         do_test(1000_000, 8, &[
             103717, 963485, 826422, 509101, 736394, 807035, 5327, 632573,
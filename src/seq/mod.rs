@@ -576,7 +576,7 @@ mod test {
         let mut nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
 
         assert_eq!(chars.choose(&mut r), Some(&'l'));
-        assert_eq!(nums.choose_mut(&mut r), Some(&mut 10));
+        assert_eq!(nums.choose_mut(&mut r), Some(&mut 3));
 
         #[cfg(feature = "alloc")]
         assert_eq!(
@@ -584,21 +584,21 @@ mod test {
                 .choose_multiple(&mut r, 8)
                 .cloned()
                 .collect::<Vec<char>>(),
-            &['d', 'm', 'b', 'n', 'c', 'k', 'h', 'e']
+            &['f', 'i', 'd', 'b', 'c', 'm', 'j', 'k']
         );
 
         #[cfg(feature = "alloc")]
-        assert_eq!(chars.choose_weighted(&mut r, |_| 1), Ok(&'f'));
+        assert_eq!(chars.choose_weighted(&mut r, |_| 1), Ok(&'l'));
         #[cfg(feature = "alloc")]
-        assert_eq!(nums.choose_weighted_mut(&mut r, |_| 1), Ok(&mut 5));
+        assert_eq!(nums.choose_weighted_mut(&mut r, |_| 1), Ok(&mut 8));
 
         let mut r = crate::test::rng(414);
         nums.shuffle(&mut r);
-        assert_eq!(nums, [9, 5, 3, 10, 7, 12, 8, 11, 6, 4, 0, 2, 1]);
+        assert_eq!(nums, [10, 11, 8, 7, 4, 6, 12, 5, 3, 0, 9, 2, 1]);
         nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
         let res = nums.partial_shuffle(&mut r, 6);
-        assert_eq!(res.0, &mut [7, 4, 8, 6, 9, 3]);
-        assert_eq!(res.1, &mut [0, 1, 2, 12, 11, 5, 10]);
+        assert_eq!(res.0, &mut [6, 10, 7, 2, 0, 8]);
+        assert_eq!(res.1, &mut [11, 1, 12, 3, 4, 5, 9]);
     }
 
     #[derive(Clone)]
@@ -899,7 +899,7 @@ mod test {
                 chunk_remaining: 32,
                 hint_total_size: false,
             }),
-            Some(39)
+            Some(70)
         );
         assert_eq!(
             choose(ChunkHintedIterator {
@@ -908,7 +908,7 @@ mod test {
                 chunk_remaining: 32,
                 hint_total_size: true,
             }),
-            Some(39)
+            Some(70)
         );
         assert_eq!(
             choose(WindowHintedIterator {
@@ -916,7 +916,7 @@ mod test {
                 window_size: 32,
                 hint_total_size: false,
             }),
-            Some(90)
+            Some(34)
         );
         assert_eq!(
             choose(WindowHintedIterator {
@@ -924,7 +924,7 @@ mod test {
                 window_size: 32,
                 hint_total_size: true,
             }),
-            Some(90)
+            Some(34)
         );
     }
 
@@ -939,7 +939,7 @@ mod test {
 
         do_test(0..4, &[0, 1, 2, 3]);
         do_test(0..8, &[0, 1, 2, 3, 4, 5, 6, 7]);
-        do_test(0..100, &[58, 78, 80, 92, 43, 8, 96, 7]);
+        do_test(0..100, &[77, 95, 38, 23, 25, 8, 58, 40]);
 
         #[cfg(feature = "alloc")]
         {
@@ -950,7 +950,7 @@ mod test {
 
             do_test(0..4, &[0, 1, 2, 3]);
             do_test(0..8, &[0, 1, 2, 3, 4, 5, 6, 7]);
-            do_test(0..100, &[58, 78, 80, 92, 43, 8, 96, 7]);
+            do_test(0..100, &[77, 95, 38, 23, 25, 8, 58, 40]);
         }
     }
 }
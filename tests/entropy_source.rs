@@ -0,0 +1,84 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The entropy source is process-wide state, so this is tested in a separate
+// binary, and all in a single test.
+
+#![cfg(all(feature = "std", feature = "std_rng"))]
+
+use std::thread;
+
+use rand::rngs::adapter::{ReadError, ReadRng, ReseedingRng};
+use rand::rngs::{OsRng, StdRng};
+use rand::{Error, RngCore, SeedableRng};
+use rand_hc::{Hc128Core, Hc128Rng};
+
+// A fake source of entropy, producing a constant byte.
+struct ConstRng(u8);
+
+impl RngCore for ConstRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for x in dest {
+            *x = self.0;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_entropy_source() {
+    rand::set_entropy_source(Box::new(ConstRng(0x42)));
+    let mut expected = StdRng::from_seed([0x42; 32]);
+    let x = expected.next_u64();
+
+    assert_eq!(OsRng.next_u64(), 0x4242424242424242);
+    assert_eq!(StdRng::from_entropy().next_u64(), x);
+
+    // use a new thread, whose `ThreadRng` is seeded from the source
+    let y = thread::spawn(|| rand::thread_rng().next_u64()).join().unwrap();
+    assert_eq!(y, x);
+
+    let mut reseeding = ReseedingRng::new(Hc128Core::from_seed([0; 32]), 0, OsRng);
+    reseeding.reseed().unwrap();
+    let mut expected = Hc128Rng::from_seed([0x42; 32]);
+    assert_eq!(reseeding.next_u64(), expected.next_u64());
+
+    // errors are passed on; reading from an empty source fails
+    rand::set_entropy_source(Box::new(ReadRng::new(&[][..])));
+    let is_read_error = |err: Option<Error>| err.map_or(false, |err| err.inner().is::<ReadError>());
+    assert!(is_read_error(StdRng::try_from_entropy().err()));
+    assert!(is_read_error(reseeding.reseed().err()));
+
+    // `try_thread_rng` reports the failure instead of panicking, and tries
+    // again on the next call
+    let (err, retried) = thread::spawn(|| {
+        let err = rand::try_thread_rng().err();
+        rand::set_entropy_source(Box::new(ConstRng(0x42)));
+        (err, rand::try_thread_rng().map(|mut rng| rng.next_u64()))
+    })
+    .join()
+    .unwrap();
+    assert!(is_read_error(err));
+    assert_eq!(retried.unwrap(), x);
+
+    rand::reset_entropy_source();
+    assert!(StdRng::try_from_entropy().is_ok());
+    assert_ne!(OsRng.next_u64(), OsRng.next_u64());
+}
@@ -0,0 +1,53 @@
+// Copyright 2018 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The master seed of `thread_rng` is process-wide state, so this is tested in
+// a separate binary, and all in a single test.
+
+#![cfg(feature = "deterministic_thread_rng")]
+
+use std::thread;
+
+use rand::rngs::thread::{clear_seed, set_seed};
+use rand::{Rng, RngCore};
+
+fn spawned() -> u64 {
+    thread::spawn(|| rand::thread_rng().next_u64()).join().unwrap()
+}
+
+#[test]
+fn test_set_seed() {
+    set_seed(42);
+    let a: [u64; 4] = rand::thread_rng().gen();
+    // exceed the reseeding threshold, which must not apply
+    let mut buf = [0u8; 1024];
+    for _ in 0..128 {
+        rand::thread_rng().fill(&mut buf[..]);
+    }
+    let b = rand::thread_rng().next_u64();
+    let c = spawned();
+
+    set_seed(42);
+    assert_eq!(a, rand::thread_rng().gen::<[u64; 4]>());
+    for _ in 0..128 {
+        rand::thread_rng().fill(&mut buf[..]);
+    }
+    assert_eq!(b, rand::thread_rng().next_u64());
+    assert_eq!(c, spawned());
+
+    set_seed(43);
+    assert_ne!(a, rand::thread_rng().gen::<[u64; 4]>());
+    assert_ne!(c, spawned());
+
+    // back to seeding from `OsRng`, in this thread and in new ones
+    set_seed(42);
+    clear_seed();
+    assert_ne!(a, rand::thread_rng().gen::<[u64; 4]>());
+    assert_ne!(c, spawned());
+    clear_seed();
+}
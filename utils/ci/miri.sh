@@ -15,4 +15,6 @@ cargo miri test --manifest-path rand_core/Cargo.toml --no-default-features
 #cargo miri test --manifest-path rand_distr/Cargo.toml # no unsafe and lots of slow tests
 cargo miri test --manifest-path rand_pcg/Cargo.toml --features=serde1
 cargo miri test --manifest-path rand_chacha/Cargo.toml --no-default-features
-cargo miri test --manifest-path rand_hc/Cargo.toml
+cargo miri test --manifest-path rand_hc/Cargo.toml --features=serde1
+cargo miri test --manifest-path rand_random123/Cargo.toml
+cargo miri test --manifest-path rand_xoshiro/Cargo.toml --features=serde1
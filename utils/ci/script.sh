@@ -31,12 +31,14 @@ main() {
     $CARGO test $TARGET --benches --features=nightly
   else
     # all stable features:
-    $CARGO test $TARGET --features=serde1,log,small_rng
+    $CARGO test $TARGET --features=serde1_std_rng,serde1_small_rng,log,deterministic_thread_rng
   fi
 
   if [ "$ALLOC" -ge 1 ]; then
     $CARGO test $TARGET --tests --no-default-features --features=alloc,getrandom,small_rng
     $CARGO test $TARGET --manifest-path rand_core/Cargo.toml --no-default-features --features=alloc
+    $CARGO test $TARGET --features=async
+    $CARGO test $TARGET --manifest-path rand_core/Cargo.toml --no-default-features --features=async
   fi
   
   $CARGO test $TARGET --tests --no-default-features
@@ -48,8 +50,11 @@ main() {
   
   $CARGO test $TARGET --manifest-path rand_distr/Cargo.toml
   $CARGO test $TARGET --manifest-path rand_pcg/Cargo.toml --features=serde1
-  $CARGO test $TARGET --manifest-path rand_chacha/Cargo.toml
-  $CARGO test $TARGET --manifest-path rand_hc/Cargo.toml
+  $CARGO test $TARGET --manifest-path rand_chacha/Cargo.toml --features=serde1
+  $CARGO test $TARGET --manifest-path rand_drbg/Cargo.toml
+  $CARGO test $TARGET --manifest-path rand_hc/Cargo.toml --features=serde1
+  $CARGO test $TARGET --manifest-path rand_random123/Cargo.toml
+  $CARGO test $TARGET --manifest-path rand_xoshiro/Cargo.toml --features=serde1
 }
 
 # we don't run the "test phase" when doing deploys